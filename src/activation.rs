@@ -0,0 +1,164 @@
+use std::f64::consts::{FRAC_1_SQRT_2, FRAC_2_SQRT_PI};
+use std::ops::Div;
+
+use crate::real::Real;
+use crate::scalar::Scalar;
+
+/// `α` of [`Sigmoid::selu`], from Klambauer et al. (2017).
+const SELU_ALPHA: f64 = 1.673_263_242_354_377_3;
+/// `λ` of [`Sigmoid::selu`].
+const SELU_LAMBDA: f64 = 1.050_700_987_355_480_5;
+
+/// Activation functions over the scalar field `T`: the logistic sigmoid
+/// and the usual neural-network nonlinearities built from it. `tanh` is
+/// [`Ops::tanh`](crate::Ops::tanh).
+///
+/// Every method is provided, so an implementor only opts in; derivatives
+/// follow from the operations each one is written with.
+///
+/// # Non-smooth points
+///
+/// Piecewise functions branch on [`Scalar::to_f64`]. At a kink they take
+/// the derivative of the piece the kink belongs to, which is:
+///
+/// - the left piece for `relu`, `leaky_relu`, `elu` and `selu`, so
+///   `relu'(0) = 0`, `leaky_relu'(0) = alpha`, `elu'(0) = alpha`;
+/// - the flat pieces for `hard_sigmoid`, so `hard_sigmoid'(±3) = 0`;
+/// - the outer pieces for `hard_swish`, so `hard_swish'(-3) = 0` and
+///   `hard_swish'(3) = 1`.
+///
+/// A NaN value takes the piece that depends on `x`. NaN then propagates,
+/// and tracers, whose value is NaN, see the dependency.
+pub trait Sigmoid<T: Real = f64>: Scalar<T>
+where
+    T: Div<Self, Output=Self> {
+    /// Logistic sigmoid `1 / (1 + exp(-x))`.
+    ///
+    /// Negative `x` use the equivalent `eˣ / (1 + eˣ)`, so no intermediate
+    /// overflows and the derivative stays finite for any finite `x`.
+    /// First-order types override this to apply `σ' = σ (1 - σ)` directly.
+    fn sigmoid(self) -> Self {
+        if self.to_f64() >= 0.0 {
+            T::one() / ((-self).exp() + T::one())
+        } else {
+            let e = self.exp();
+            e.clone() / (e + T::one())
+        }
+    }
+
+    /// `max(x, 0)`, with derivative 0 at `x = 0`.
+    fn relu(self) -> Self {
+        if self.to_f64() <= 0.0 {
+            Self::zero()
+        } else {
+            self
+        }
+    }
+
+    /// `x` for `x > 0`, `alpha x` otherwise.
+    fn leaky_relu(self, alpha: f64) -> Self {
+        if self.to_f64() <= 0.0 {
+            self * T::from_f64(alpha)
+        } else {
+            self
+        }
+    }
+
+    /// Exponential linear unit: `x` for `x > 0`, `alpha (eˣ - 1)` otherwise.
+    fn elu(self, alpha: f64) -> Self {
+        if self.to_f64() <= 0.0 {
+            self.exp_m1() * T::from_f64(alpha)
+        } else {
+            self
+        }
+    }
+
+    /// Scaled ELU `λ elu(x, α)` with the self-normalizing constants
+    /// `α ≈ 1.6733` and `λ ≈ 1.0507`.
+    fn selu(self) -> Self {
+        self.elu(SELU_ALPHA) * T::from_f64(SELU_LAMBDA)
+    }
+
+    /// Gaussian error linear unit `x Φ(x) = x (1 + erf(x / √2)) / 2`.
+    fn gelu(self) -> Self {
+        let cdf = ((self.clone() * T::from_f64(FRAC_1_SQRT_2)).erf() + T::one()) * T::from_f64(0.5);
+        self * cdf
+    }
+
+    /// The tanh approximation of [`gelu`](Sigmoid::gelu),
+    /// `x (1 + tanh(√(2/π) (x + 0.044715 x³))) / 2`.
+    fn gelu_tanh(self) -> Self {
+        let inner = (self.clone().powi(3) * T::from_f64(0.044715) + self.clone())
+            * T::from_f64(FRAC_2_SQRT_PI * FRAC_1_SQRT_2);
+        self * (inner.tanh() + T::one()) * T::from_f64(0.5)
+    }
+
+    /// `x σ(beta x)`.
+    fn swish(self, beta: f64) -> Self {
+        self.clone() * (self * T::from_f64(beta)).sigmoid()
+    }
+
+    /// Sigmoid linear unit `x σ(x)`, i.e. `swish(1)`.
+    fn silu(self) -> Self {
+        self.clone() * self.sigmoid()
+    }
+
+    /// `x tanh(softplus(x))`.
+    fn mish(self) -> Self {
+        self.clone() * self.softplus().tanh()
+    }
+
+    /// `ln(1 + eˣ)`, as `x + ln(1 + e⁻ˣ)` for positive `x` so that it
+    /// neither overflows nor loses precision.
+    fn softplus(self) -> Self {
+        if self.to_f64() > 0.0 {
+            self.clone() + (-self).exp().ln_1p()
+        } else {
+            self.exp().ln_1p()
+        }
+    }
+
+    /// `x / (1 + |x|)`.
+    fn softsign(self) -> Self {
+        self.clone() / (self.abs() + T::one())
+    }
+
+    /// Piecewise-linear sigmoid `clamp(x / 6 + 1/2, 0, 1)`.
+    fn hard_sigmoid(self) -> Self {
+        let x = self.to_f64();
+        if x <= -3.0 {
+            Self::zero()
+        } else if x >= 3.0 {
+            Self::one()
+        } else {
+            self / T::from_f64(6.0) + T::from_f64(0.5)
+        }
+    }
+
+    /// `x hard_sigmoid(x)`.
+    fn hard_swish(self) -> Self {
+        let x = self.to_f64();
+        if x <= -3.0 {
+            Self::zero()
+        } else if x >= 3.0 {
+            self
+        } else {
+            self.clone() * (self / T::from_f64(6.0) + T::from_f64(0.5))
+        }
+    }
+
+    /// `ln σ(x) = -softplus(-x)`, finite for any finite `x`.
+    fn log_sigmoid(self) -> Self {
+        -(-self).softplus()
+    }
+
+    /// Inverse of [`sigmoid`](Sigmoid::sigmoid), `ln(p / (1 - p))` for `p`
+    /// in `(0, 1)`, with derivative `1 / (p (1 - p))`. Infinite at `p = 0`
+    /// and `p = 1`.
+    fn logit(self) -> Self {
+        self.clone().ln() - (-self).ln_1p()
+    }
+}
+
+impl Sigmoid<f32> for f32 {}
+impl Sigmoid<f64> for f64 {}
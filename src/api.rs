@@ -0,0 +1,143 @@
+use crate::dual::Dual;
+use crate::hvp::hvp;
+use crate::matrix::Matrix;
+use crate::real::Real;
+use crate::reverse::Tape;
+
+/// A function `ℝⁿ → ℝ` written once over any [`Real`], so the
+/// differentiation entry points can pick the mode.
+///
+/// Rust closures can't be generic, so implement this on a (usually
+/// zero-sized) struct:
+///
+/// ```
+/// use dual::{gradient, Function, Real};
+///
+/// struct Rosenbrock;
+///
+/// impl Function for Rosenbrock {
+///     fn eval<T: Real>(&self, x: &[T]) -> T {
+///         let a = T::one() - x[0];
+///         let b = x[1] - x[0] * x[0];
+///         a * a + T::from_f64(100.0) * b * b
+///     }
+/// }
+///
+/// assert_eq!(gradient(&Rosenbrock, &[1.0, 1.0]), vec![0.0, 0.0]);
+/// ```
+pub trait Function {
+    fn eval<T: Real>(&self, x: &[T]) -> T;
+}
+
+/// A function `ℝⁿ → ℝᵐ` written once over any [`Real`]; see [`Function`].
+pub trait VectorFunction {
+    fn eval<T: Real>(&self, x: &[T]) -> Vec<T>;
+}
+
+/// Up to this many inputs, [`gradient`] uses one forward pass per input
+/// instead of recording a tape.
+const FORWARD_GRADIENT_MAX_INPUTS: usize = 4;
+
+/// Returns `(f(x), f'(x))`, in forward mode.
+pub fn derivative<F>(f: F, x: f64) -> (f64, f64)
+where
+    F: FnOnce(Dual) -> Dual,
+{
+    let y = f(Dual::variable(x));
+    (y.value(), y.deriv())
+}
+
+/// Returns `(f(x), ∇f(x) · v)` from a single forward pass seeded with `v`.
+///
+/// # Panics
+///
+/// Panics if `x` and `v` have different lengths.
+pub fn directional_derivative<F>(f: F, x: &[f64], v: &[f64]) -> (f64, f64)
+where
+    F: FnOnce(&[Dual]) -> Dual,
+{
+    assert_eq!(x.len(), v.len(), "directional_derivative: x and v have different lengths");
+    let inputs: Vec<_> = x.iter().zip(v).map(|(&xi, &vi)| Dual::new(xi, vi)).collect();
+    let y = f(&inputs);
+    (y.value(), y.deriv())
+}
+
+/// Returns `∇f(x)`.
+///
+/// Small inputs take one forward pass each; beyond a handful of inputs a
+/// single reverse sweep over a tape is cheaper. Both modes give the same
+/// result, singular partials included.
+pub fn gradient<F: Function>(f: &F, x: &[f64]) -> Vec<f64> {
+    if x.len() <= FORWARD_GRADIENT_MAX_INPUTS {
+        (0..x.len())
+            .map(|i| f.eval(&seed(x, i)).deriv())
+            .collect()
+    } else {
+        let tape = Tape::new();
+        let vars = tape.vars(x);
+        f.eval(&vars).backward().wrt_all(&vars)
+    }
+}
+
+/// Returns the `m × n` Jacobian of `f` at `x`.
+///
+/// Forward mode costs one pass per input and reverse mode one sweep per
+/// output, so this picks whichever of `n` and `m` is smaller, after one
+/// plain evaluation of `f` to learn `m`.
+pub fn jacobian<F: VectorFunction>(f: &F, x: &[f64]) -> Matrix {
+    let n = x.len();
+    // One plain evaluation tells how many outputs there are.
+    let m = f.eval(x).len();
+    let mut jac = Matrix::zeros(m, n);
+    if n <= m {
+        for j in 0..n {
+            for (i, y) in f.eval(&seed(x, j)).iter().enumerate() {
+                jac[(i, j)] = y.deriv();
+            }
+        }
+    } else {
+        let tape = Tape::new();
+        let vars = tape.vars(x);
+        for (i, y) in f.eval(&vars).iter().enumerate() {
+            let grad = y.backward();
+            for (j, v) in vars.iter().enumerate() {
+                jac[(i, j)] = grad.wrt(v);
+            }
+        }
+    }
+    jac
+}
+
+/// Returns the Hessian of `f` at `x`, one reverse-over-forward
+/// Hessian-vector product ([`hvp`]) per column.
+///
+/// The result is symmetrized, so rounding in the two mixed partials can't
+/// make it asymmetric.
+pub fn hessian<F: Function>(f: &F, x: &[f64]) -> Matrix {
+    let n = x.len();
+    let mut h = Matrix::zeros(n, n);
+    let mut e = vec![0.0; n];
+    for j in 0..n {
+        e[j] = 1.0;
+        for (i, hij) in hvp(|x| f.eval(x), x, &e).into_iter().enumerate() {
+            h[(i, j)] = hij;
+        }
+        e[j] = 0.0;
+    }
+    for i in 0..n {
+        for j in 0..i {
+            let avg = 0.5 * (h[(i, j)] + h[(j, i)]);
+            h[(i, j)] = avg;
+            h[(j, i)] = avg;
+        }
+    }
+    h
+}
+
+/// Inputs with only the `i`-th seeded.
+fn seed(x: &[f64], i: usize) -> Vec<Dual> {
+    x.iter()
+        .enumerate()
+        .map(|(j, &xj)| if i == j { Dual::variable(xj) } else { Dual::constant(xj) })
+        .collect()
+}
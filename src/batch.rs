@@ -0,0 +1,125 @@
+use std::num::NonZeroUsize;
+use std::panic;
+use std::thread;
+
+use crate::dual::Dual;
+use crate::real::Real;
+
+/// Evaluates a function of one [`Dual`] over many samples on a pool of
+/// scoped threads.
+///
+/// Samples are cut into chunks of a fixed size and each chunk is reduced
+/// on its own; partial results are then combined in chunk order. Which
+/// thread handles which chunk never affects the result, so sums and means
+/// are bit-for-bit identical for any thread count given the same chunk
+/// size.
+#[derive(Debug, Copy, Clone)]
+pub struct Batch {
+    threads: usize,
+    chunk_size: usize,
+}
+
+impl Default for Batch {
+    fn default() -> Self {
+        Self {
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            chunk_size: 1024,
+        }
+    }
+}
+
+impl Batch {
+    /// Uses every available core and chunks of 1024 samples.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of worker threads (at least one).
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Sets the number of samples per chunk (at least one). This fixes
+    /// the summation order, so keep it constant for reproducible results.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Returns `f(x + 1 ε)` for every sample `x`, in input order.
+    pub fn eval<T, F>(&self, f: F, xs: &[T]) -> Vec<Dual<T>>
+    where
+        T: Real + Send + Sync,
+        F: Fn(Dual<T>) -> Dual<T> + Sync,
+    {
+        self.map_chunks(xs, |chunk| {
+            chunk.iter().map(|&x| f(Dual::variable(x))).collect::<Vec<_>>()
+        })
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// Returns the sum of `f(x + 1 ε)` over all samples: the total value
+    /// and the sum of per-sample derivatives.
+    pub fn sum<T, F>(&self, f: F, xs: &[T]) -> Dual<T>
+    where
+        T: Real + Send + Sync,
+        F: Fn(Dual<T>) -> Dual<T> + Sync,
+    {
+        let zero = Dual::constant(T::zero());
+        self.map_chunks(xs, |chunk| {
+            chunk.iter().fold(zero, |acc, &x| acc + f(Dual::variable(x)))
+        })
+        .into_iter()
+        .fold(zero, |acc, partial| acc + partial)
+    }
+
+    /// Returns the mean of `f(x + 1 ε)` over all samples. NaN if `xs` is
+    /// empty.
+    pub fn mean<T, F>(&self, f: F, xs: &[T]) -> Dual<T>
+    where
+        T: Real + Send + Sync,
+        F: Fn(Dual<T>) -> Dual<T> + Sync,
+    {
+        self.sum(f, xs) / T::from_f64(xs.len() as f64)
+    }
+
+    /// Applies `g` to every chunk, thread `t` taking chunks `t`,
+    /// `t + threads`, ..., and returns the results in chunk order.
+    fn map_chunks<T, R, G>(&self, xs: &[T], g: G) -> Vec<R>
+    where
+        T: Sync,
+        R: Send,
+        G: Fn(&[T]) -> R + Sync,
+    {
+        let chunks: Vec<&[T]> = xs.chunks(self.chunk_size).collect();
+        let threads = self.threads.min(chunks.len());
+        if threads <= 1 {
+            return chunks.into_iter().map(g).collect();
+        }
+
+        let mut results: Vec<Option<R>> = (0..chunks.len()).map(|_| None).collect();
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|t| {
+                    let (chunks, g) = (&chunks, &g);
+                    scope.spawn(move || {
+                        (t..chunks.len())
+                            .step_by(threads)
+                            .map(|i| (i, g(chunks[i])))
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            for worker in workers {
+                let done = worker.join().unwrap_or_else(|payload| panic::resume_unwind(payload));
+                for (i, r) in done {
+                    results[i] = Some(r);
+                }
+            }
+        });
+        results.into_iter().map(|r| r.expect("every chunk is evaluated")).collect()
+    }
+}
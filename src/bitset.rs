@@ -0,0 +1,41 @@
+/// A growable set of small integers, one bit per element.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub(crate) fn singleton(i: usize) -> Self {
+        let mut set = Self::default();
+        set.insert(i);
+        set
+    }
+
+    pub(crate) fn insert(&mut self, i: usize) {
+        let (word, bit) = (i / 64, i % 64);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << bit;
+    }
+
+    pub(crate) fn union(&self, other: &Self) -> Self {
+        let (long, short) = if self.words.len() >= other.words.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut words = long.words.clone();
+        for (w, s) in words.iter_mut().zip(&short.words) {
+            *w |= s;
+        }
+        Self { words }
+    }
+
+    /// Iterates the elements in increasing order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(k, &word)| {
+            (0..64).filter(move |bit| word >> bit & 1 == 1).map(move |bit| k * 64 + bit)
+        })
+    }
+}
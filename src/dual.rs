@@ -0,0 +1,757 @@
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::num::ParseFloatError;
+use std::ops::{Neg, Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign, DivAssign};
+use std::str::FromStr;
+
+use crate::activation::Sigmoid;
+use crate::ops::{impl_ops_by_chain_rule, scale};
+use crate::real::Real;
+use crate::scalar::{impl_mixed_scalars, Scalar};
+
+/// A dual number `x + dx ε` with `ε² = 0`.
+///
+/// `Dual<T>` is itself [`Real`], so duals nest: `Dual<Dual<f64>>` carries
+/// two independent infinitesimals and yields second derivatives and
+/// forward-over-forward Hessian-vector products. The nesting depth is what
+/// tells the two tangents apart: the outer perturbation lives in the primal
+/// `Dual<f64>`, the inner one in the outer `dx`. Values from an enclosing
+/// derivative enter an inner one as constants, which the mixed operators
+/// below do automatically; [`Dual::diff`] seeds the inner variable one
+/// level up so the two can't share a depth.
+///
+/// The arithmetic operators take a `Dual<T>`, a `T` or an integer on
+/// either side, by value or by reference. Floats only mix at their own
+/// width, in both directions: with `f32` operators on `Dual<f64>` as well,
+/// a literal like the `2.0` in `(x * 2.0).sin()` would match two impls and
+/// need an annotation. Convert with `as` instead:
+///
+/// ```compile_fail
+/// use dual::Dual;
+///
+/// let scale = 2.0f32;
+/// let y = Dual::variable(1.0f64) * scale;
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dual<T = f64> {
+    x: T,
+    dx: T,
+}
+
+impl<T: Real> Dual<T> {
+    /// Creates a dual number `x + dx ε`.
+    pub fn new(x: T, dx: T) -> Self {
+        Self { x, dx }
+    }
+
+    /// Creates an independent variable, i.e. `x + 1 ε`.
+    pub fn variable(x: T) -> Self {
+        Self { x, dx: T::one() }
+    }
+
+    /// Creates a constant, i.e. `x + 0 ε`.
+    pub fn constant(x: T) -> Self {
+        Self { x, dx: T::zero() }
+    }
+
+    /// Returns the primal value.
+    pub fn value(&self) -> T {
+        self.x
+    }
+
+    /// Returns the derivative (tangent) part.
+    pub fn deriv(&self) -> T {
+        self.dx
+    }
+
+    /// Returns the derivative of `f` at `x`, for derivatives nested inside
+    /// another forward-mode computation.
+    ///
+    /// `x` is a value of the enclosing computation and `f` runs one level
+    /// up, on `Dual<Self>`, so any enclosing variable it captures is lifted
+    /// to a constant there and the result keeps the enclosing tangent:
+    ///
+    /// ```
+    /// use dual::{derivative, Dual};
+    ///
+    /// // d/dx (x · d/dy (x y)) = d/dx x² = 2x
+    /// let outer = |x: Dual<f64>| {
+    ///     let inner = Dual::diff(|y| x * y, Dual::constant(3.0));
+    ///     x * inner
+    /// };
+    /// assert_eq!(derivative(outer, 5.0).1, 10.0);
+    /// ```
+    ///
+    /// Seeding at the enclosing level is what keeps the two perturbations
+    /// apart. A plain `f64` seed would put the inner variable on the same
+    /// `ε` as `x` and silently add them together, so it doesn't type-check:
+    ///
+    /// ```compile_fail
+    /// use dual::{derivative, Dual};
+    ///
+    /// let outer = |x: Dual<f64>| x * Dual::diff(|y| x * y, 3.0);
+    /// derivative(outer, 5.0);
+    /// ```
+    ///
+    /// At the top level, use [`derivative`](crate::derivative) or
+    /// [`Dual::variable`] directly.
+    pub fn diff<F>(f: F, x: Self) -> Self
+    where
+        F: FnOnce(Dual<Self>) -> Dual<Self>,
+    {
+        f(Dual::variable(x)).dx
+    }
+
+    fn chain(self, fx: T, dfx: T) -> Self {
+        Self {
+            x: fx,
+            dx: scale(dfx, self.dx),
+        }
+    }
+
+    fn chain2(self, other: Self, fx: T, dfdx: T, dfdy: T) -> Self {
+        Self {
+            x: fx,
+            dx: scale(dfdx, self.dx) + scale(dfdy, other.dx),
+        }
+    }
+}
+
+/// Comparisons and selections by primal value.
+///
+/// `==`, `<` and the other comparison operators look at the value only, so
+/// duals with equal values and different tangents compare equal; use
+/// [`Dual::eq_full`] to compare tangents as well. [`min`](Dual::min),
+/// [`max`](Dual::max) and [`clamp`](Dual::clamp) return the selected
+/// operand with its own tangent, and on a tie they select `self`, so the
+/// derivative there is that of the `self` branch. `abs` is
+/// [`Ops::abs`](crate::Ops::abs), whose tangent at zero follows the sign of
+/// the zero.
+impl<T: Real + PartialOrd> Dual<T> {
+    /// Returns the operand with the smaller value, `self` on a tie. A NaN
+    /// value loses to a number, as with `f64::min`.
+    pub fn min(self, other: Self) -> Self {
+        if other.x < self.x || self.x.to_f64().is_nan() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Returns the operand with the larger value, `self` on a tie. A NaN
+    /// value loses to a number, as with `f64::max`.
+    pub fn max(self, other: Self) -> Self {
+        if other.x > self.x || self.x.to_f64().is_nan() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Restricts the value to `[lo, hi]`.
+    ///
+    /// Outside the interval the result is the bound as a constant, with a
+    /// zero tangent. Inside it, and exactly at a bound, `self` is returned
+    /// unchanged with its tangent. A NaN value passes through.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lo > hi` or either bound is NaN.
+    pub fn clamp(self, lo: T, hi: T) -> Self {
+        assert!(lo <= hi, "Dual::clamp: need lo <= hi and no NaN bound");
+        if self.x < lo {
+            Self::constant(lo)
+        } else if self.x > hi {
+            Self::constant(hi)
+        } else {
+            self
+        }
+    }
+
+    /// Returns whether value and tangent are both equal, as tests usually
+    /// want. Nested duals are compared through every level.
+    pub fn eq_full(&self, other: &Self) -> bool
+    where
+        T: FullEq,
+    {
+        FullEq::eq_full(self, other)
+    }
+}
+
+/// Equality of every part of a number, derivative parts included, where
+/// `==` on duals looks only at the value.
+pub trait FullEq {
+    fn eq_full(&self, other: &Self) -> bool;
+}
+
+macro_rules! impl_full_eq {
+    ($($t:ty),*) => {$(
+        impl FullEq for $t {
+            fn eq_full(&self, other: &Self) -> bool {
+                self == other
+            }
+        }
+    )*};
+}
+
+impl_full_eq!(f32, f64);
+
+impl<T: FullEq> FullEq for Dual<T> {
+    fn eq_full(&self, other: &Self) -> bool {
+        self.x.eq_full(&other.x) && self.dx.eq_full(&other.dx)
+    }
+}
+
+impl<T: Real + PartialEq> PartialEq for Dual<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x
+    }
+}
+
+impl<T: Real + PartialOrd> PartialOrd for Dual<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.x.partial_cmp(&other.x)
+    }
+}
+
+impl<T: Real + PartialEq> PartialEq<T> for Dual<T> {
+    fn eq(&self, other: &T) -> bool {
+        self.x == *other
+    }
+}
+
+impl<T: Real + PartialOrd> PartialOrd<T> for Dual<T> {
+    fn partial_cmp(&self, other: &T) -> Option<Ordering> {
+        self.x.partial_cmp(other)
+    }
+}
+
+impl<T: Real> Neg for Dual<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            dx: -self.dx,
+        }
+    }
+}
+
+impl<T: Real> Add for Dual<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x + rhs.x,
+            dx: self.dx + rhs.dx,
+        }
+    }
+}
+
+impl<T: Real> Sub for Dual<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            x: self.x - rhs.x,
+            dx: self.dx - rhs.dx,
+        }
+    }
+}
+
+impl<T: Real> Mul for Dual<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            x: self.x * rhs.x,
+            dx: scale(self.x, rhs.dx) + scale(rhs.x, self.dx),
+        }
+    }
+}
+
+impl<T: Real> Div for Dual<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        Self {
+            x: self.x / rhs.x,
+            dx: (scale(rhs.x, self.dx) - scale(self.x, rhs.dx)) / (rhs.x * rhs.x),
+        }
+    }
+}
+
+impl<T: Real> Add<T> for Dual<T> {
+    type Output = Self;
+    fn add(self, rhs: T) -> Self {
+        Self {
+            x: self.x + rhs,
+            dx: self.dx,
+        }
+    }
+}
+
+impl<T: Real> Sub<T> for Dual<T> {
+    type Output = Self;
+    fn sub(self, rhs: T) -> Self {
+        Self {
+            x: self.x - rhs,
+            dx: self.dx,
+        }
+    }
+}
+
+impl<T: Real> Mul<T> for Dual<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Self {
+            x: self.x * rhs,
+            dx: self.dx * rhs,
+        }
+    }
+}
+
+impl<T: Real> Div<T> for Dual<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self {
+        Self {
+            x: self.x / rhs,
+            dx: self.dx / rhs,
+        }
+    }
+}
+
+// Plain floats compare against a dual's value, like the dual itself does.
+macro_rules! impl_scalar_cmp {
+    ($($t:ty),*) => {$(
+        impl PartialEq<Dual<$t>> for $t {
+            fn eq(&self, other: &Dual<$t>) -> bool {
+                *self == other.x
+            }
+        }
+
+        impl PartialOrd<Dual<$t>> for $t {
+            fn partial_cmp(&self, other: &Dual<$t>) -> Option<Ordering> {
+                self.partial_cmp(&other.x)
+            }
+        }
+    )*};
+}
+
+impl_scalar_cmp!(f32, f64);
+
+impl<T: Real> Scalar<T> for Dual<T> {
+    fn zero() -> Self {
+        Self::constant(T::zero())
+    }
+
+    fn one() -> Self {
+        Self::constant(T::one())
+    }
+
+    fn from_f64(x: f64) -> Self {
+        Self::constant(T::from_f64(x))
+    }
+
+    fn to_f64(&self) -> f64 {
+        self.x.to_f64()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.x.is_zero() && self.dx.is_zero()
+    }
+}
+
+// Mixing with itself makes `Dual<T>` a `Real`, so it can be nested.
+impl_mixed_scalars!(
+    [] Dual [],
+    |lhs, rhs| Dual {
+        x: lhs / rhs.x,
+        dx: -(lhs * rhs.dx) / (rhs.x * rhs.x),
+    },
+    sigmoid: first_order
+);
+
+// A `Dual<T>` meeting a `Dual<Dual<T>>` is a constant at the inner level.
+macro_rules! impl_lift {
+    ($($op:ident $method:ident),*) => {$(
+        impl<T: Real> $op<Dual<Dual<T>>> for Dual<T> {
+            type Output = Dual<Dual<T>>;
+
+            fn $method(self, rhs: Dual<Dual<T>>) -> Dual<Dual<T>> {
+                Dual::constant(self).$method(rhs)
+            }
+        }
+    )*};
+}
+
+impl_lift!(Add add, Sub sub, Mul mul, Div div);
+
+impl<T: Real> Sigmoid<Dual<T>> for Dual<Dual<T>> {}
+
+// Plain scalars mixed into a once-nested dual, in both directions.
+macro_rules! impl_nested_scalar {
+    ($($t:ty),*) => {$(
+        impl_nested_scalar!(@ops $t, Add add, Sub sub, Mul mul, Div div);
+
+        impl Scalar<$t> for Dual<Dual<$t>> {
+            fn zero() -> Self {
+                Dual::constant(Dual::constant(0.0))
+            }
+
+            fn one() -> Self {
+                Dual::constant(Dual::constant(1.0))
+            }
+
+            fn from_f64(x: f64) -> Self {
+                Dual::constant(Dual::constant(x as $t))
+            }
+
+            fn to_f64(&self) -> f64 {
+                self.x.x as f64
+            }
+
+            fn is_zero(&self) -> bool {
+                <Self as Scalar<Dual<$t>>>::is_zero(self)
+            }
+        }
+    )*};
+    (@ops $t:ty, $($op:ident $method:ident),*) => {$(
+        impl $op<$t> for Dual<Dual<$t>> {
+            type Output = Self;
+
+            fn $method(self, rhs: $t) -> Self {
+                self.$method(Dual::constant(rhs))
+            }
+        }
+
+        impl $op<Dual<Dual<$t>>> for $t {
+            type Output = Dual<Dual<$t>>;
+
+            fn $method(self, rhs: Dual<Dual<$t>>) -> Dual<Dual<$t>> {
+                Dual::constant(Dual::constant(self)).$method(rhs)
+            }
+        }
+    )*};
+}
+
+impl_nested_scalar!(f32, f64);
+
+// By-reference operands forward to the by-value impls; `Dual` is `Copy`,
+// so each is a dereference.
+macro_rules! forward_ref_binop {
+    ([$($gen:tt)*] $op:ident $method:ident, $lhs:ty, $rhs:ty) => {
+        impl<'a, $($gen)*> $op<$rhs> for &'a $lhs {
+            type Output = <$lhs as $op<$rhs>>::Output;
+
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                (*self).$method(rhs)
+            }
+        }
+
+        impl<'a, $($gen)*> $op<&'a $rhs> for $lhs {
+            type Output = <$lhs as $op<$rhs>>::Output;
+
+            fn $method(self, rhs: &'a $rhs) -> Self::Output {
+                self.$method(*rhs)
+            }
+        }
+
+        impl<'a, 'b, $($gen)*> $op<&'b $rhs> for &'a $lhs {
+            type Output = <$lhs as $op<$rhs>>::Output;
+
+            fn $method(self, rhs: &'b $rhs) -> Self::Output {
+                (*self).$method(*rhs)
+            }
+        }
+    };
+}
+
+// Integers convert through `T::from_f64`, so they are exact up to 2^53.
+// Unlike a bare `T`, an integer on the left is allowed generically.
+macro_rules! impl_int {
+    ($($i:ty),*) => {$(
+        impl_int!(@ops $i, Add add, Sub sub, Mul mul, Div div);
+    )*};
+    (@ops $i:ty, $($op:ident $method:ident),*) => {$(
+        impl<T: Real> $op<$i> for Dual<T> {
+            type Output = Self;
+
+            fn $method(self, rhs: $i) -> Self {
+                self.$method(T::from_f64(rhs as f64))
+            }
+        }
+
+        impl<T: Real> $op<Dual<T>> for $i {
+            type Output = Dual<T>;
+
+            fn $method(self, rhs: Dual<T>) -> Dual<T> {
+                Dual::constant(T::from_f64(self as f64)).$method(rhs)
+            }
+        }
+
+        forward_ref_binop!([T: Real] $op $method, Dual<T>, $i);
+        forward_ref_binop!([T: Real] $op $method, $i, Dual<T>);
+    )*};
+}
+
+impl_int!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+macro_rules! impl_ref_ops {
+    ($($op:ident $method:ident),*) => {$(
+        forward_ref_binop!([T: Real] $op $method, Dual<T>, Dual<T>);
+        forward_ref_binop!([T: Real] $op $method, Dual<T>, T);
+        forward_ref_binop!([] $op $method, f32, Dual<f32>);
+        forward_ref_binop!([] $op $method, f64, Dual<f64>);
+    )*};
+}
+
+impl_ref_ops!(Add add, Sub sub, Mul mul, Div div);
+
+impl<T: Real> Neg for &Dual<T> {
+    type Output = Dual<T>;
+    fn neg(self) -> Dual<T> {
+        -*self
+    }
+}
+
+// Compound assignment accepts every right-hand side the binary operator
+// does: duals, scalars, integers and references to them.
+macro_rules! impl_assign {
+    ($($op:ident $method:ident $bin:ident $bin_method:ident),*) => {$(
+        impl<T: Real, R> $op<R> for Dual<T>
+        where
+            Dual<T>: $bin<R, Output = Dual<T>>,
+        {
+            fn $method(&mut self, rhs: R) {
+                *self = (*self).$bin_method(rhs);
+            }
+        }
+    )*};
+}
+
+impl_assign!(
+    AddAssign add_assign Add add,
+    SubAssign sub_assign Sub sub,
+    MulAssign mul_assign Mul mul,
+    DivAssign div_assign Div div
+);
+
+impl<T: Real> Sum for Dual<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::constant(T::zero()), |acc, x| acc + x)
+    }
+}
+
+impl<'a, T: Real> Sum<&'a Dual<T>> for Dual<T> {
+    fn sum<I: Iterator<Item = &'a Dual<T>>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// The product rule applies factor by factor, so the tangent is
+/// `Σᵢ dxᵢ Πⱼ≠ᵢ xⱼ` with no division by the values.
+impl<T: Real> Product for Dual<T> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::constant(T::one()), |acc, x| acc * x)
+    }
+}
+
+impl<'a, T: Real> Product<&'a Dual<T>> for Dual<T> {
+    fn product<I: Iterator<Item = &'a Dual<T>>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+impl_ops_by_chain_rule!([T: Real] Dual<T>, T);
+
+/// A formatting trait method for one part, like `Display::fmt`.
+type PartFn<T> = fn(&T, &mut fmt::Formatter) -> fmt::Result;
+
+/// Writes `x + dxε`, or `x - |dx|ε` for a tangent with the sign bit set.
+///
+/// Each part is formatted with `part` under the caller's precision only,
+/// and `+` applies to the value alone. Width, fill and alignment then pad
+/// the whole text once, right-aligned by default like a plain number; with
+/// the `0` flag the padding is zeros after the value's sign instead.
+fn write_parts<T: Neg<Output = T>>(
+    f: &mut fmt::Formatter,
+    x: T,
+    dx: T,
+    dx_negative: bool,
+    part: PartFn<T>,
+) -> fmt::Result {
+    let precision = f.precision();
+    let mut text = part_text(&x, part, f.sign_plus(), precision);
+    if dx_negative {
+        text.push_str(" - ");
+        text.push_str(&part_text(&-dx, part, false, precision));
+    } else {
+        text.push_str(" + ");
+        text.push_str(&part_text(&dx, part, false, precision));
+    }
+    text.push('ε');
+
+    let len = text.chars().count();
+    let Some(padding) = f.width().and_then(|w| w.checked_sub(len)) else {
+        return f.write_str(&text);
+    };
+    if f.sign_aware_zero_pad() {
+        let sign = if text.starts_with(['+', '-']) { 1 } else { 0 };
+        f.write_str(&text[..sign])?;
+        (0..padding).try_for_each(|_| f.write_str("0"))?;
+        return f.write_str(&text[sign..]);
+    }
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        Some(fmt::Alignment::Right) | None => (padding, 0),
+    };
+    let fill = f.fill();
+    (0..before).try_for_each(|_| write!(f, "{fill}"))?;
+    f.write_str(&text)?;
+    (0..after).try_for_each(|_| write!(f, "{fill}"))
+}
+
+/// Formats one part of a dual number with `part`, optionally signed and at
+/// a fixed precision.
+fn part_text<T>(v: &T, part: PartFn<T>, plus: bool, precision: Option<usize>) -> String {
+    struct Part<'a, T>(&'a T, PartFn<T>);
+
+    impl<T> fmt::Display for Part<'_, T> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            (self.1)(self.0, f)
+        }
+    }
+
+    let v = Part(v, part);
+    match (plus, precision) {
+        (false, None) => format!("{v}"),
+        (false, Some(p)) => format!("{v:.p$}"),
+        (true, None) => format!("{v:+}"),
+        (true, Some(p)) => format!("{v:+.p$}"),
+    }
+}
+
+/// The value text of a dual number and, if present, whether its tangent is
+/// negated together with the tangent text.
+type Parts<'a> = (&'a str, Option<(bool, &'a str)>);
+
+/// Splits `x + dxε` or `x - dxε` into the value text, whether the tangent
+/// is negated, and the tangent text. Text without a trailing `ε` is a value
+/// alone.
+fn split_parts(s: &str) -> Result<Parts<'_>, ParseDualError> {
+    let s = s.trim();
+    let Some(body) = s.strip_suffix('ε') else {
+        return Ok((s, None));
+    };
+    // The separator is the last sign that isn't leading, part of an
+    // exponent such as `1e-5`, or the tangent's own sign as in `1 + -2ε`.
+    let sep = body
+        .char_indices()
+        .rev()
+        .filter(|&(_, c)| c == '+' || c == '-')
+        .find(|&(i, _)| {
+            let before = body[..i].trim_end();
+            !before.is_empty() && !before.ends_with(['e', 'E', '+', '-'])
+        });
+    match sep {
+        Some((i, c)) => Ok((body[..i].trim(), Some((c == '-', body[i + 1..].trim())))),
+        None => Err(ParseDualError(ParseErrorKind::Syntax)),
+    }
+}
+
+/// Splits an optional leading sign off one part, allowing whitespace after
+/// it as after the separator, so `- 2` reads like `-2`. Returns whether the
+/// part is negated and its unsigned text.
+fn split_sign(s: &str) -> Result<(bool, &str), ParseDualError> {
+    let (negative, rest) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let rest = rest.trim_start();
+    if rest.starts_with(['+', '-']) {
+        return Err(ParseDualError(ParseErrorKind::Syntax));
+    }
+    Ok((negative, rest))
+}
+
+/// The error returned when parsing a [`Dual`] from text fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDualError(ParseErrorKind);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ParseErrorKind {
+    Syntax,
+    Float(ParseFloatError),
+}
+
+impl fmt::Display for ParseDualError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            ParseErrorKind::Syntax => f.write_str("expected a dual number of the form `x + dxε`"),
+            ParseErrorKind::Float(e) => write!(f, "invalid part in dual number: {e}"),
+        }
+    }
+}
+
+impl Error for ParseDualError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.0 {
+            ParseErrorKind::Syntax => None,
+            ParseErrorKind::Float(e) => Some(e),
+        }
+    }
+}
+
+impl From<ParseFloatError> for ParseDualError {
+    fn from(e: ParseFloatError) -> Self {
+        ParseDualError(ParseErrorKind::Float(e))
+    }
+}
+
+// Text form `x + dxε`. Precision and the `e` format apply to both parts;
+// without a precision each part prints its shortest exact form, so parsing
+// the output gives back the same bits.
+macro_rules! impl_text {
+    ($($t:ty),*) => {$(
+        /// Formats as `x + dxε`, e.g. `{:.10}` gives
+        /// `0.8414709848 + 0.5403023059ε`. Precision applies to each part,
+        /// `+` to the value, and width to the whole text.
+        impl fmt::Display for Dual<$t> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write_parts(f, self.x, self.dx, self.dx.is_sign_negative(), fmt::Display::fmt)
+            }
+        }
+
+        /// Formats as `x + dxε` in scientific notation, e.g.
+        /// `8.414709848e-1 + 5.403023059e-1ε`.
+        impl fmt::LowerExp for Dual<$t> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write_parts(f, self.x, self.dx, self.dx.is_sign_negative(), fmt::LowerExp::fmt)
+            }
+        }
+
+        /// Parses the [`Display`](fmt::Display) and
+        /// [`LowerExp`](fmt::LowerExp) forms. A plain number parses as a
+        /// constant. Each part may carry one sign of its own, with or
+        /// without a space after it.
+        impl FromStr for Dual<$t> {
+            type Err = ParseDualError;
+
+            fn from_str(s: &str) -> Result<Self, ParseDualError> {
+                let part = |text: &str, negated: bool| -> Result<$t, ParseDualError> {
+                    let (negative, text) = split_sign(text)?;
+                    let v: $t = text.parse()?;
+                    Ok(if negative != negated { -v } else { v })
+                };
+                let (x, dx) = split_parts(s)?;
+                let dx = match dx {
+                    Some((negated, dx)) => part(dx, negated)?,
+                    None => 0.0,
+                };
+                Ok(Dual { x: part(x, false)?, dx })
+            }
+        }
+    )*};
+}
+
+impl_text!(f32, f64);
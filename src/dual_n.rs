@@ -0,0 +1,195 @@
+use std::ops::{Neg, Add, Sub, Mul, Div};
+
+use crate::ops::{impl_ops_by_chain_rule, scale};
+use crate::real::Real;
+use crate::scalar::{impl_mixed_scalars, Scalar};
+
+/// A dual number carrying a gradient with respect to `N` inputs:
+/// `x + Σ dx[i] εᵢ` with `εᵢ εⱼ = 0`.
+///
+/// Seed input `i` with [`DualN::variable`] (or all of them at once with
+/// [`DualN::variables`]) and a single evaluation yields the full gradient.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DualN<const N: usize, T = f64> {
+    x: T,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
+    dx: [T; N],
+}
+
+impl<const N: usize, T: Real> DualN<N, T> {
+    /// Creates a dual number with the given value and gradient.
+    pub fn new(x: T, dx: [T; N]) -> Self {
+        Self { x, dx }
+    }
+
+    /// Creates the `i`-th independent variable, i.e. `x + 1 εᵢ`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= N`.
+    pub fn variable(x: T, i: usize) -> Self {
+        let mut dx = [T::zero(); N];
+        dx[i] = T::one();
+        Self { x, dx }
+    }
+
+    /// Creates a constant with a zero gradient.
+    pub fn constant(x: T) -> Self {
+        Self {
+            x,
+            dx: [T::zero(); N],
+        }
+    }
+
+    /// Seeds every input at once: the `i`-th result is `xs[i] + 1 εᵢ`.
+    pub fn variables(xs: [T; N]) -> [Self; N] {
+        std::array::from_fn(|i| Self::variable(xs[i], i))
+    }
+
+    /// Returns the primal value.
+    pub fn value(&self) -> T {
+        self.x
+    }
+
+    /// Returns the gradient.
+    pub fn deriv(&self) -> [T; N] {
+        self.dx
+    }
+
+    fn chain(self, fx: T, dfx: T) -> Self {
+        Self {
+            x: fx,
+            dx: self.dx.map(|d| scale(dfx, d)),
+        }
+    }
+
+    fn chain2(self, other: Self, fx: T, dfdx: T, dfdy: T) -> Self {
+        Self {
+            x: fx,
+            dx: std::array::from_fn(|i| scale(dfdx, self.dx[i]) + scale(dfdy, other.dx[i])),
+        }
+    }
+}
+
+impl<const N: usize, T: Real> Neg for DualN<N, T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            dx: self.dx.map(|d| -d),
+        }
+    }
+}
+
+impl<const N: usize, T: Real> Add for DualN<N, T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x + rhs.x,
+            dx: std::array::from_fn(|i| self.dx[i] + rhs.dx[i]),
+        }
+    }
+}
+
+impl<const N: usize, T: Real> Sub for DualN<N, T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            x: self.x - rhs.x,
+            dx: std::array::from_fn(|i| self.dx[i] - rhs.dx[i]),
+        }
+    }
+}
+
+impl<const N: usize, T: Real> Mul for DualN<N, T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let (x, y) = (self.x, rhs.x);
+        self.chain2(rhs, x * y, y, x)
+    }
+}
+
+impl<const N: usize, T: Real> Div for DualN<N, T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let (x, y) = (self.x, rhs.x);
+        self.chain2(rhs, x / y, T::one() / y, -x / (y * y))
+    }
+}
+
+impl<const N: usize, T: Real> Add<T> for DualN<N, T> {
+    type Output = Self;
+    fn add(self, rhs: T) -> Self {
+        Self {
+            x: self.x + rhs,
+            dx: self.dx,
+        }
+    }
+}
+
+impl<const N: usize, T: Real> Sub<T> for DualN<N, T> {
+    type Output = Self;
+    fn sub(self, rhs: T) -> Self {
+        Self {
+            x: self.x - rhs,
+            dx: self.dx,
+        }
+    }
+}
+
+impl<const N: usize, T: Real> Mul<T> for DualN<N, T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Self {
+            x: self.x * rhs,
+            dx: self.dx.map(|d| d * rhs),
+        }
+    }
+}
+
+impl<const N: usize, T: Real> Div<T> for DualN<N, T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self {
+        Self {
+            x: self.x / rhs,
+            dx: self.dx.map(|d| d / rhs),
+        }
+    }
+}
+
+impl<const N: usize, T: Real> Scalar<T> for DualN<N, T> {
+    fn zero() -> Self {
+        Self::constant(T::zero())
+    }
+
+    fn one() -> Self {
+        Self::constant(T::one())
+    }
+
+    fn from_f64(x: f64) -> Self {
+        Self::constant(T::from_f64(x))
+    }
+
+    fn to_f64(&self) -> f64 {
+        self.x.to_f64()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.x.is_zero() && self.dx.iter().all(T::is_zero)
+    }
+}
+
+impl_mixed_scalars!(
+    [const N: usize,] DualN [N],
+    |lhs, rhs| {
+        let scale = -lhs / (rhs.x * rhs.x);
+        DualN {
+            x: lhs / rhs.x,
+            dx: rhs.dx.map(|d| scale * d),
+        }
+    },
+    sigmoid: first_order
+);
+
+impl_ops_by_chain_rule!([const N: usize, T: Real] DualN<N, T>, T);
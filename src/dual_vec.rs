@@ -0,0 +1,215 @@
+use std::ops::{Neg, Add, Sub, Mul, Div};
+
+use crate::ops::{impl_ops_by_chain_rule, scale};
+use crate::real::Real;
+use crate::scalar::{impl_mixed_scalars, Scalar};
+
+/// A dual number whose gradient length is chosen at runtime.
+///
+/// An empty tangent stands for the zero gradient, so constants don't
+/// allocate and combine with variables of any length.
+///
+/// # Panics
+///
+/// Binary operations between two values whose tangents are both non-empty
+/// but of different lengths panic with a "tangent length mismatch" message.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DualVec<T = f64> {
+    x: T,
+    dx: Vec<T>,
+}
+
+impl<T: Real> DualVec<T> {
+    /// Creates a dual number with the given value and gradient.
+    pub fn new(x: T, dx: Vec<T>) -> Self {
+        Self { x, dx }
+    }
+
+    /// Creates the `i`-th of `n` independent variables, i.e. `x + 1 εᵢ`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= n`.
+    pub fn variable(x: T, i: usize, n: usize) -> Self {
+        let mut dx = vec![T::zero(); n];
+        dx[i] = T::one();
+        Self { x, dx }
+    }
+
+    /// Creates a constant. Its tangent is empty and doesn't allocate.
+    pub fn constant(x: T) -> Self {
+        Self { x, dx: Vec::new() }
+    }
+
+    /// Seeds every input at once: the `i`-th result is `xs[i] + 1 εᵢ`.
+    pub fn variables(xs: &[T]) -> Vec<Self> {
+        let n = xs.len();
+        xs.iter()
+            .enumerate()
+            .map(|(i, &x)| Self::variable(x, i, n))
+            .collect()
+    }
+
+    /// Returns the primal value.
+    pub fn value(&self) -> T {
+        self.x
+    }
+
+    /// Returns the gradient. Empty if `self` doesn't depend on any input.
+    pub fn deriv(&self) -> &[T] {
+        &self.dx
+    }
+
+    /// Returns `true` if the tangent is empty, i.e. `self` is a constant.
+    pub fn is_constant(&self) -> bool {
+        self.dx.is_empty()
+    }
+
+    fn chain(mut self, fx: T, dfx: T) -> Self {
+        self.dx.iter_mut().for_each(|d| *d = scale(dfx, *d));
+        Self { x: fx, dx: self.dx }
+    }
+
+    fn chain2(self, other: Self, fx: T, dfdx: T, dfdy: T) -> Self {
+        Self {
+            x: fx,
+            dx: axpby(dfdx, self.dx, dfdy, other.dx),
+        }
+    }
+}
+
+/// Computes `a dx + b dy`, treating an empty tangent as zero.
+fn axpby<T: Real>(a: T, mut dx: Vec<T>, b: T, mut dy: Vec<T>) -> Vec<T> {
+    match (dx.is_empty(), dy.is_empty()) {
+        (true, true) => dx,
+        (false, true) => {
+            dx.iter_mut().for_each(|d| *d = scale(a, *d));
+            dx
+        }
+        (true, false) => {
+            dy.iter_mut().for_each(|d| *d = scale(b, *d));
+            dy
+        }
+        (false, false) => {
+            assert_eq!(
+                dx.len(),
+                dy.len(),
+                "DualVec tangent length mismatch: {} vs {}",
+                dx.len(),
+                dy.len()
+            );
+            dx.iter_mut().zip(dy).for_each(|(p, q)| *p = scale(a, *p) + scale(b, q));
+            dx
+        }
+    }
+}
+
+impl<T: Real> Neg for DualVec<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        let x = -self.x;
+        self.chain(x, -T::one())
+    }
+}
+
+impl<T: Real> Add for DualVec<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let x = self.x + rhs.x;
+        self.chain2(rhs, x, T::one(), T::one())
+    }
+}
+
+impl<T: Real> Sub for DualVec<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        let x = self.x - rhs.x;
+        self.chain2(rhs, x, T::one(), -T::one())
+    }
+}
+
+impl<T: Real> Mul for DualVec<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let (x, y) = (self.x, rhs.x);
+        self.chain2(rhs, x * y, y, x)
+    }
+}
+
+impl<T: Real> Div for DualVec<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let (x, y) = (self.x, rhs.x);
+        self.chain2(rhs, x / y, T::one() / y, -x / (y * y))
+    }
+}
+
+impl<T: Real> Add<T> for DualVec<T> {
+    type Output = Self;
+    fn add(self, rhs: T) -> Self {
+        Self {
+            x: self.x + rhs,
+            dx: self.dx,
+        }
+    }
+}
+
+impl<T: Real> Sub<T> for DualVec<T> {
+    type Output = Self;
+    fn sub(self, rhs: T) -> Self {
+        Self {
+            x: self.x - rhs,
+            dx: self.dx,
+        }
+    }
+}
+
+impl<T: Real> Mul<T> for DualVec<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        let x = self.x * rhs;
+        self.chain(x, rhs)
+    }
+}
+
+impl<T: Real> Div<T> for DualVec<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self {
+        let x = self.x / rhs;
+        self.chain(x, T::one() / rhs)
+    }
+}
+
+impl<T: Real> Scalar<T> for DualVec<T> {
+    fn zero() -> Self {
+        Self::constant(T::zero())
+    }
+
+    fn one() -> Self {
+        Self::constant(T::one())
+    }
+
+    fn from_f64(x: f64) -> Self {
+        Self::constant(T::from_f64(x))
+    }
+
+    fn to_f64(&self) -> f64 {
+        self.x.to_f64()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.x.is_zero() && self.dx.iter().all(T::is_zero)
+    }
+}
+
+impl_mixed_scalars!(
+    [] DualVec [],
+    |lhs, rhs| {
+        let x = rhs.x;
+        rhs.chain(lhs / x, -lhs / (x * x))
+    },
+    sigmoid: first_order
+);
+
+impl_ops_by_chain_rule!([T: Real] DualVec<T>, T);
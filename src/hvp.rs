@@ -0,0 +1,79 @@
+use std::ops::{Add, Sub, Mul, Div};
+
+use crate::activation::Sigmoid;
+use crate::dual::Dual;
+use crate::reverse::{Tape, Var};
+use crate::scalar::Scalar;
+
+/// Computes the Hessian-vector product `H(x) v` without forming `H`.
+///
+/// `f` is evaluated once on `Dual<Var>` inputs seeded with `x + v ε`, so the
+/// tangent of the output is the directional derivative `∇f(x) · v` recorded
+/// on a tape. One reverse sweep over it then gives `∇(∇f · v) = H v`. The
+/// cost is a small constant multiple of one gradient evaluation.
+///
+/// # Panics
+///
+/// Panics if `x` and `v` have different lengths.
+pub fn hvp<F>(f: F, x: &[f64], v: &[f64]) -> Vec<f64>
+where
+    F: for<'t> Fn(&[Dual<Var<'t>>]) -> Dual<Var<'t>>,
+{
+    assert_eq!(x.len(), v.len(), "hvp: x and v have different lengths");
+    let tape = Tape::new();
+    let leaves = tape.vars(x);
+    let inputs: Vec<_> = leaves
+        .iter()
+        .zip(v)
+        .map(|(&xi, &vi)| Dual::new(xi, Var::constant(vi)))
+        .collect();
+    f(&inputs).deriv().backward().wrt_all(&leaves)
+}
+
+// Plain `f64` operands for `Dual<Var>`, so model code written against
+// `Dual<f64>` compiles unchanged when differentiated a second time.
+macro_rules! impl_scalar {
+    ($($op:ident $method:ident),*) => {$(
+        impl<'t> $op<f64> for Dual<Var<'t>> {
+            type Output = Self;
+
+            fn $method(self, rhs: f64) -> Self {
+                self.$method(Var::constant(rhs))
+            }
+        }
+
+        impl<'t> $op<Dual<Var<'t>>> for f64 {
+            type Output = Dual<Var<'t>>;
+
+            fn $method(self, rhs: Dual<Var<'t>>) -> Dual<Var<'t>> {
+                Dual::constant(Var::constant(self)).$method(rhs)
+            }
+        }
+    )*};
+}
+
+impl_scalar!(Add add, Sub sub, Mul mul, Div div);
+
+impl<'t> Sigmoid<f64> for Dual<Var<'t>> {}
+
+impl<'t> Scalar for Dual<Var<'t>> {
+    fn zero() -> Self {
+        Dual::constant(Var::constant(0.0))
+    }
+
+    fn one() -> Self {
+        Dual::constant(Var::constant(1.0))
+    }
+
+    fn from_f64(x: f64) -> Self {
+        Dual::constant(Var::constant(x))
+    }
+
+    fn to_f64(&self) -> f64 {
+        self.value().value()
+    }
+
+    fn is_zero(&self) -> bool {
+        <Self as Scalar<Self>>::is_zero(self)
+    }
+}
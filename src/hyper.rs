@@ -0,0 +1,458 @@
+use std::f64::consts::{FRAC_2_SQRT_PI, LN_2, LN_10};
+use std::ops::{Neg, Add, Sub, Mul, Div};
+
+use crate::ops::{scale, Ops};
+use crate::real::Real;
+use crate::scalar::{impl_mixed_scalars, Scalar};
+
+/// A hyper-dual number `x + e1 ε₁ + e2 ε₂ + e12 ε₁ε₂` with
+/// `ε₁² = ε₂² = 0` and `ε₁ε₂ ≠ 0`.
+///
+/// Seeding `ε₁` and `ε₂` along two inputs makes the `ε₁ε₂` part the exact
+/// mixed second derivative, with no truncation or cancellation error.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HyperDual<T = f64> {
+    x: T,
+    e1: T,
+    e2: T,
+    e12: T,
+}
+
+impl<T: Real> HyperDual<T> {
+    /// Creates a hyper-dual number from its four parts.
+    pub fn new(x: T, e1: T, e2: T, e12: T) -> Self {
+        Self { x, e1, e2, e12 }
+    }
+
+    /// Creates an independent variable seeded along both `ε₁` and `ε₂`, so
+    /// that `eps1()` is `f'` and `eps12()` is `f''`.
+    pub fn variable(x: T) -> Self {
+        Self {
+            x,
+            e1: T::one(),
+            e2: T::one(),
+            e12: T::zero(),
+        }
+    }
+
+    /// Creates a constant.
+    pub fn constant(x: T) -> Self {
+        Self {
+            x,
+            e1: T::zero(),
+            e2: T::zero(),
+            e12: T::zero(),
+        }
+    }
+
+    /// Returns the primal value.
+    pub fn value(&self) -> T {
+        self.x
+    }
+
+    /// Returns the `ε₁` part.
+    pub fn eps1(&self) -> T {
+        self.e1
+    }
+
+    /// Returns the `ε₂` part.
+    pub fn eps2(&self) -> T {
+        self.e2
+    }
+
+    /// Returns the `ε₁ε₂` part.
+    pub fn eps12(&self) -> T {
+        self.e12
+    }
+
+    /// Evaluates `f` at `x` and returns `(f(x), f'(x), f''(x))`.
+    pub fn derivatives<F>(f: F, x: T) -> (T, T, T)
+    where
+        F: FnOnce(Self) -> Self,
+    {
+        let y = f(Self::variable(x));
+        (y.x, y.e1, y.e12)
+    }
+
+    /// Computes the Hessian of `f` at `x`, one evaluation per entry of the
+    /// upper triangle.
+    pub fn hessian<F>(f: F, x: &[T]) -> Vec<Vec<T>>
+    where
+        F: Fn(&[Self]) -> Self,
+    {
+        let n = x.len();
+        let mut h = vec![vec![T::zero(); n]; n];
+        let mut args: Vec<Self> = x.iter().map(|&xi| Self::constant(xi)).collect();
+        for i in 0..n {
+            for j in i..n {
+                args[i].e1 = T::one();
+                args[j].e2 = T::one();
+                let y = f(&args);
+                h[i][j] = y.e12;
+                h[j][i] = y.e12;
+                args[i].e1 = T::zero();
+                args[j].e2 = T::zero();
+            }
+        }
+        h
+    }
+
+    /// Applies `f` with `f(x) = g0`, `f'(x) = g1` and `f''(x) = g2`.
+    fn chain(self, g0: T, g1: T, g2: T) -> Self {
+        Self {
+            x: g0,
+            e1: scale(g1, self.e1),
+            e2: scale(g1, self.e2),
+            e12: scale(g1, self.e12) + scale(g2, self.e1 * self.e2),
+        }
+    }
+
+    /// Applies `f(a, b)` given its value, gradient and Hessian at
+    /// `(self, other)`.
+    #[allow(clippy::too_many_arguments)]
+    fn chain2(self, other: Self, f: T, fa: T, fb: T, faa: T, fab: T, fbb: T) -> Self {
+        let (a, b) = (self, other);
+        Self {
+            x: f,
+            e1: scale(fa, a.e1) + scale(fb, b.e1),
+            e2: scale(fa, a.e2) + scale(fb, b.e2),
+            e12: scale(fa, a.e12)
+                + scale(fb, b.e12)
+                + scale(faa, a.e1 * a.e2)
+                + scale(fab, a.e1 * b.e2 + a.e2 * b.e1)
+                + scale(fbb, b.e1 * b.e2),
+        }
+    }
+}
+
+impl<T: Real> Neg for HyperDual<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            e1: -self.e1,
+            e2: -self.e2,
+            e12: -self.e12,
+        }
+    }
+}
+
+impl<T: Real> Add for HyperDual<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x + rhs.x,
+            e1: self.e1 + rhs.e1,
+            e2: self.e2 + rhs.e2,
+            e12: self.e12 + rhs.e12,
+        }
+    }
+}
+
+impl<T: Real> Sub for HyperDual<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            x: self.x - rhs.x,
+            e1: self.e1 - rhs.e1,
+            e2: self.e2 - rhs.e2,
+            e12: self.e12 - rhs.e12,
+        }
+    }
+}
+
+impl<T: Real> Mul for HyperDual<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let (x, y) = (self.x, rhs.x);
+        self.chain2(rhs, x * y, y, x, T::zero(), T::one(), T::zero())
+    }
+}
+
+impl<T: Real> Div for HyperDual<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let (x, y) = (self.x, rhs.x);
+        let inv = T::one() / y;
+        let q = x * inv;
+        let two = T::from_f64(2.0);
+        self.chain2(rhs, q, inv, -q * inv, T::zero(), -inv * inv, two * q * inv * inv)
+    }
+}
+
+impl<T: Real> Add<T> for HyperDual<T> {
+    type Output = Self;
+    fn add(self, rhs: T) -> Self {
+        Self {
+            x: self.x + rhs,
+            ..self
+        }
+    }
+}
+
+impl<T: Real> Sub<T> for HyperDual<T> {
+    type Output = Self;
+    fn sub(self, rhs: T) -> Self {
+        Self {
+            x: self.x - rhs,
+            ..self
+        }
+    }
+}
+
+impl<T: Real> Mul<T> for HyperDual<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Self {
+            x: self.x * rhs,
+            e1: self.e1 * rhs,
+            e2: self.e2 * rhs,
+            e12: self.e12 * rhs,
+        }
+    }
+}
+
+impl<T: Real> Div<T> for HyperDual<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self {
+        Self {
+            x: self.x / rhs,
+            e1: self.e1 / rhs,
+            e2: self.e2 / rhs,
+            e12: self.e12 / rhs,
+        }
+    }
+}
+
+impl<T: Real> Scalar<T> for HyperDual<T> {
+    fn zero() -> Self {
+        Self::constant(T::zero())
+    }
+
+    fn one() -> Self {
+        Self::constant(T::one())
+    }
+
+    fn from_f64(x: f64) -> Self {
+        Self::constant(T::from_f64(x))
+    }
+
+    fn to_f64(&self) -> f64 {
+        self.x.to_f64()
+    }
+
+    fn is_zero(&self) -> bool {
+        [self.x, self.e1, self.e2, self.e12].iter().all(T::is_zero)
+    }
+}
+
+impl_mixed_scalars!(
+    [] HyperDual [],
+    |lhs, rhs| {
+        let inv = 1.0 / rhs.x;
+        let q = lhs * inv;
+        rhs.chain(q, -q * inv, 2.0 * q * inv * inv)
+    },
+    sigmoid: second_order
+);
+
+impl<T: Real> Ops for HyperDual<T> {
+    fn exp(self) -> Self {
+        let exp = self.x.exp();
+        self.chain(exp, exp, exp)
+    }
+
+    fn ln(self) -> Self {
+        let inv = T::one() / self.x;
+        self.chain(self.x.ln(), inv, -inv * inv)
+    }
+
+    fn sin(self) -> Self {
+        let (sin, cos) = (self.x.sin(), self.x.cos());
+        self.chain(sin, cos, -sin)
+    }
+
+    fn cos(self) -> Self {
+        let (sin, cos) = (self.x.sin(), self.x.cos());
+        self.chain(cos, -sin, -cos)
+    }
+
+    fn tan(self) -> Self {
+        let tan = self.x.tan();
+        let sec2 = tan * tan + T::one();
+        self.chain(tan, sec2, T::from_f64(2.0) * tan * sec2)
+    }
+
+    fn powi(self, n: i32) -> Self {
+        let x = self.x;
+        let nf = T::from_f64(n as f64);
+        // Constant and linear powers have exactly zero higher derivatives;
+        // the general factors would be `0 · ∞` at `x = 0`.
+        let g1 = if n == 0 { T::zero() } else { nf * x.powi(n - 1) };
+        let g2 = match n {
+            0 | 1 => T::zero(),
+            _ => nf * T::from_f64((n - 1) as f64) * x.powi(n - 2),
+        };
+        self.chain(x.powi(n), g1, g2)
+    }
+
+    fn sqrt(self) -> Self {
+        let sqrt = self.x.sqrt();
+        let g1 = T::one() / (T::from_f64(2.0) * sqrt);
+        self.chain(sqrt, g1, -g1 / (T::from_f64(2.0) * self.x))
+    }
+
+    fn cbrt(self) -> Self {
+        let cbrt = self.x.cbrt();
+        let g1 = T::one() / (T::from_f64(3.0) * cbrt * cbrt);
+        self.chain(cbrt, g1, -T::from_f64(2.0) * g1 / (T::from_f64(3.0) * self.x))
+    }
+
+    fn powf(self, n: f64) -> Self {
+        let x = self.x;
+        let g1 = if n == 0.0 { T::zero() } else { T::from_f64(n) * x.powf(n - 1.0) };
+        let g2 = if n == 0.0 || n == 1.0 {
+            T::zero()
+        } else {
+            T::from_f64(n * (n - 1.0)) * x.powf(n - 2.0)
+        };
+        self.chain(x.powf(n), g1, g2)
+    }
+
+    fn pow(self, y: Self) -> Self {
+        let (a, b) = (self.x, y.x);
+        let pow = a.pow(b);
+        let pm1 = a.pow(b - T::one());
+        // As in `powf`, an exactly zero `b` or `b - 1` zeroes its factor
+        // rather than meeting an infinite power of `a = 0`.
+        let fa = if b.is_zero() { T::zero() } else { b * pm1 };
+        let faa = if b.is_zero() || (b - T::one()).is_zero() {
+            T::zero()
+        } else {
+            b * (b - T::one()) * a.pow(b - T::from_f64(2.0))
+        };
+        let ln = a.ln();
+        let fab = pm1 * (T::one() + b * ln);
+        self.chain2(y, pow, fa, pow * ln, faa, fab, pow * ln * ln)
+    }
+
+    fn exp2(self) -> Self {
+        let exp2 = self.x.exp2();
+        let ln2 = T::from_f64(LN_2);
+        self.chain(exp2, exp2 * ln2, exp2 * ln2 * ln2)
+    }
+
+    fn exp_m1(self) -> Self {
+        let exp = self.x.exp();
+        self.chain(self.x.exp_m1(), exp, exp)
+    }
+
+    fn ln_1p(self) -> Self {
+        let inv = T::one() / (self.x + T::one());
+        self.chain(self.x.ln_1p(), inv, -inv * inv)
+    }
+
+    fn log2(self) -> Self {
+        let inv = T::one() / self.x;
+        let k = T::from_f64(1.0 / LN_2);
+        self.chain(self.x.log2(), k * inv, -k * inv * inv)
+    }
+
+    fn log10(self) -> Self {
+        let inv = T::one() / self.x;
+        let k = T::from_f64(1.0 / LN_10);
+        self.chain(self.x.log10(), k * inv, -k * inv * inv)
+    }
+
+    fn log(self, base: f64) -> Self {
+        let inv = T::one() / self.x;
+        let k = T::from_f64(1.0 / base.ln());
+        self.chain(self.x.log(base), k * inv, -k * inv * inv)
+    }
+
+    fn asin(self) -> Self {
+        let x = self.x;
+        let s = T::one() - x * x;
+        let g1 = T::one() / s.sqrt();
+        self.chain(x.asin(), g1, x * g1 / s)
+    }
+
+    fn acos(self) -> Self {
+        let x = self.x;
+        let s = T::one() - x * x;
+        let g1 = T::one() / s.sqrt();
+        self.chain(x.acos(), -g1, -x * g1 / s)
+    }
+
+    fn atan(self) -> Self {
+        let x = self.x;
+        let g1 = T::one() / (x * x + T::one());
+        self.chain(x.atan(), g1, -T::from_f64(2.0) * x * g1 * g1)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        let (a, b) = (self.x, other.x);
+        let r2 = a * a + b * b;
+        let r4 = r2 * r2;
+        let two_ab = T::from_f64(2.0) * a * b;
+        self.chain2(other, a.atan2(b), b / r2, -a / r2, -two_ab / r4, (a * a - b * b) / r4, two_ab / r4)
+    }
+
+    fn sinh(self) -> Self {
+        let (sinh, cosh) = (self.x.sinh(), self.x.cosh());
+        self.chain(sinh, cosh, sinh)
+    }
+
+    fn cosh(self) -> Self {
+        let (sinh, cosh) = (self.x.sinh(), self.x.cosh());
+        self.chain(cosh, sinh, cosh)
+    }
+
+    fn tanh(self) -> Self {
+        let tanh = self.x.tanh();
+        let sech2 = T::one() - tanh * tanh;
+        self.chain(tanh, sech2, -T::from_f64(2.0) * tanh * sech2)
+    }
+
+    fn asinh(self) -> Self {
+        let x = self.x;
+        let s = x * x + T::one();
+        let g1 = T::one() / s.sqrt();
+        self.chain(x.asinh(), g1, -x * g1 / s)
+    }
+
+    fn acosh(self) -> Self {
+        let x = self.x;
+        let s = x * x - T::one();
+        let g1 = T::one() / s.sqrt();
+        self.chain(x.acosh(), g1, -x * g1 / s)
+    }
+
+    fn atanh(self) -> Self {
+        let x = self.x;
+        let g1 = T::one() / (T::one() - x * x);
+        self.chain(x.atanh(), g1, T::from_f64(2.0) * x * g1 * g1)
+    }
+
+    fn erf(self) -> Self {
+        let x = self.x;
+        let g1 = T::from_f64(FRAC_2_SQRT_PI) * (-(x * x)).exp();
+        self.chain(x.erf(), g1, -T::from_f64(2.0) * x * g1)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        let (a, b) = (self.x, other.x);
+        let h = a.hypot(b);
+        let h3 = h * h * h;
+        self.chain2(other, h, a / h, b / h, b * b / h3, -a * b / h3, a * a / h3)
+    }
+
+    fn abs(self) -> Self {
+        self.chain(self.x.abs(), self.x.signum(), T::zero())
+    }
+
+    fn signum(self) -> Self {
+        self.chain(self.x.signum(), T::zero(), T::zero())
+    }
+}
@@ -0,0 +1,66 @@
+//! Automatic differentiation with dual numbers, plus a tape-based
+//! reverse mode.
+//!
+//! # Serialization
+//!
+//! With the `serde` feature, the forward-mode number types and the
+//! derivative results implement `Serialize` and `Deserialize` as structs
+//! with these fields:
+//!
+//! | Type | Fields |
+//! |------|--------|
+//! | [`Dual`] | `x`, `dx` |
+//! | [`DualN`] | `x`, `dx` (a tuple of `N` values) |
+//! | [`DualVec`] | `x`, `dx` (a sequence) |
+//! | [`SparseDual`] | `x`, `dx` (a sequence of `(index, partial)` pairs) |
+//! | [`HyperDual`] | `x`, `e1`, `e2`, `e12` |
+//! | [`Taylor`] | `x`, `c` (a tuple of `K` coefficients) |
+//! | [`Matrix`] | `rows`, `cols`, `data` (row-major) |
+//! | [`Sparsity`] | `rows`, `cols`, `row_ptr`, `col_idx` |
+//! | [`SparseMatrix`] | `pattern`, `values` |
+//!
+//! so a `Dual` is `{"x": 1.0, "dx": 2.0}` in JSON. Deserializing a
+//! `SparseDual` sorts and merges its pairs like [`SparseDual::new`]; the
+//! matrix types reject data inconsistent with their shape.
+
+mod activation;
+mod api;
+mod batch;
+mod bitset;
+mod dual;
+mod dual_n;
+mod dual_vec;
+mod hvp;
+mod hyper;
+mod matrix;
+mod ops;
+mod real;
+mod reverse;
+mod scalar;
+#[cfg(feature = "serde")]
+mod serde_array;
+mod softmax;
+mod sparse;
+mod sparsity;
+mod special;
+mod taylor;
+mod tracer;
+
+pub use activation::Sigmoid;
+pub use api::{derivative, directional_derivative, gradient, hessian, jacobian, Function, VectorFunction};
+pub use batch::Batch;
+pub use dual::{Dual, FullEq, ParseDualError};
+pub use dual_n::DualN;
+pub use dual_vec::DualVec;
+pub use hvp::hvp;
+pub use hyper::HyperDual;
+pub use matrix::Matrix;
+pub use ops::Ops;
+pub use real::Real;
+pub use reverse::{Grad, Tape, Var};
+pub use scalar::Scalar;
+pub use softmax::{cross_entropy, log_softmax, logsumexp, nll, softmax};
+pub use sparse::SparseDual;
+pub use sparsity::{hessian_sparsity, jacobian_sparsity, sparse_jacobian, SparseJacobian, SparseMatrix, Sparsity};
+pub use taylor::Taylor;
+pub use tracer::{HessianTape, HessianTracer, TraceTape, Tracer};
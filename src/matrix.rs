@@ -0,0 +1,94 @@
+use std::ops::{Index, IndexMut};
+
+/// A dense row-major matrix of `f64`, as returned by
+/// [`jacobian`](crate::jacobian) and [`hessian`](crate::hessian).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "MatrixParts"))]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+/// The serialized form of a [`Matrix`], checked for a consistent shape on
+/// the way in.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct MatrixParts {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<MatrixParts> for Matrix {
+    type Error = &'static str;
+
+    fn try_from(parts: MatrixParts) -> Result<Self, Self::Error> {
+        match parts.rows.checked_mul(parts.cols) {
+            Some(len) if len == parts.data.len() => Ok(Self {
+                rows: parts.rows,
+                cols: parts.cols,
+                data: parts.data,
+            }),
+            _ => Err("Matrix data has the wrong length"),
+        }
+    }
+}
+
+impl Matrix {
+    /// Creates a `rows × cols` matrix of zeros.
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self {
+            rows,
+            cols,
+            data: vec![0.0; rows * cols],
+        }
+    }
+
+    /// Creates a matrix from row-major data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data.len() != rows * cols`.
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<f64>) -> Self {
+        assert_eq!(data.len(), rows * cols, "Matrix data has the wrong length");
+        Self { rows, cols, data }
+    }
+
+    /// Returns the number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns row `i` as a slice.
+    pub fn row(&self, i: usize) -> &[f64] {
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    /// Returns the entries in row-major order.
+    pub fn as_slice(&self) -> &[f64] {
+        &self.data
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+    fn index(&self, (i, j): (usize, usize)) -> &f64 {
+        assert!(i < self.rows && j < self.cols, "Matrix index out of bounds");
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut f64 {
+        assert!(i < self.rows && j < self.cols, "Matrix index out of bounds");
+        &mut self.data[i * self.cols + j]
+    }
+}
@@ -0,0 +1,260 @@
+use crate::real::Real;
+
+/// Elementary functions with derivative propagation.
+///
+/// Real-valued exponents and bases (`powf`, `log`) are taken as `f64`, the
+/// same way `powi` takes an `i32`, so the signatures don't depend on the
+/// scalar a type is built over.
+///
+/// The infinite and NaN derivatives noted below only reach the inputs an
+/// operand actually depends on; a constant stays a constant everywhere.
+pub trait Ops {
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn powi(self, n: i32) -> Self;
+
+    /// Square root. The derivative `1 / (2√x)` is infinite at `x = 0`.
+    fn sqrt(self) -> Self;
+    /// Cube root. The derivative `1 / (3∛x²)` is infinite at `x = 0`.
+    fn cbrt(self) -> Self;
+    /// `x^n` for a constant exponent. The derivative `n x^(n-1)` is
+    /// infinite at `x = 0` when `n < 1`, except that `n = 0` gives exactly
+    /// zero.
+    fn powf(self, n: f64) -> Self;
+    /// `x^y` where the exponent carries a derivative too. The `ln x` term
+    /// makes the derivative NaN for `x <= 0`, unless the exponent is a
+    /// constant, in which case this matches `powf`.
+    fn pow(self, y: Self) -> Self;
+    fn exp2(self) -> Self;
+    /// `exp(x) - 1`, accurate near zero.
+    fn exp_m1(self) -> Self;
+    /// `ln(1 + x)`, accurate near zero. Singular at `x = -1`.
+    fn ln_1p(self) -> Self;
+    /// Base-2 logarithm. Singular at `x = 0`.
+    fn log2(self) -> Self;
+    /// Base-10 logarithm. Singular at `x = 0`.
+    fn log10(self) -> Self;
+    /// Logarithm in a constant base. Singular at `x = 0`.
+    fn log(self, base: f64) -> Self;
+
+    /// Arcsine. The derivative is infinite at `x = ±1`.
+    fn asin(self) -> Self;
+    /// Arccosine. The derivative is infinite at `x = ±1`.
+    fn acos(self) -> Self;
+    fn atan(self) -> Self;
+    /// Four-quadrant arctangent of `self / other`. The derivative is NaN at
+    /// the origin.
+    fn atan2(self, other: Self) -> Self;
+
+    fn sinh(self) -> Self;
+    fn cosh(self) -> Self;
+    fn tanh(self) -> Self;
+    fn asinh(self) -> Self;
+    /// Inverse hyperbolic cosine. The derivative is infinite at `x = 1`.
+    fn acosh(self) -> Self;
+    /// Inverse hyperbolic tangent. The derivative is infinite at `x = ±1`.
+    fn atanh(self) -> Self;
+    /// Error function `2/√π ∫₀ˣ e^(-t²) dt`, with derivative
+    /// `2/√π e^(-x²)`.
+    fn erf(self) -> Self;
+
+    /// `√(x² + y²)`. The derivative is NaN at the origin.
+    fn hypot(self, other: Self) -> Self;
+    /// Absolute value. The derivative is `signum(x)`, so at zero it follows
+    /// the sign of the zero: `+1` for `0.0`, `-1` for `-0.0`.
+    fn abs(self) -> Self;
+    /// Sign of `x`. Piecewise constant, so the derivative is always zero.
+    fn signum(self) -> Self;
+}
+
+/// Implements [`Ops`] for a first-order forward-mode type from the
+/// derivative table below.
+///
+/// The type needs a `value()` accessor for its primal and two private
+/// helpers that apply the chain rule to its tangent:
+///
+/// - `chain(self, fx, dfx)` for `f(x)` with `f'(x) = dfx`,
+/// - `chain2(self, other, fx, dfdx, dfdy)` for `f(x, y)` with partials
+///   `dfdx` and `dfdy`,
+///
+/// Both helpers apply partials through [`scale`], so a tangent entry that
+/// is exactly zero stays zero even where a partial is infinite or NaN,
+/// like `sqrt` at zero or the `ln x` in `pow`.
+macro_rules! impl_ops_by_chain_rule {
+    ([$($gen:tt)*] $ty:ty, $t:ident) => {
+        impl<$($gen)*> $crate::ops::Ops for $ty {
+            fn exp(self) -> Self {
+                let exp = self.value().exp();
+                self.chain(exp, exp)
+            }
+
+            fn ln(self) -> Self {
+                let x = self.value();
+                self.chain(x.ln(), $t::one() / x)
+            }
+
+            fn sin(self) -> Self {
+                let x = self.value();
+                self.chain(x.sin(), x.cos())
+            }
+
+            fn cos(self) -> Self {
+                let x = self.value();
+                self.chain(x.cos(), -x.sin())
+            }
+
+            fn tan(self) -> Self {
+                let tan = self.value().tan();
+                self.chain(tan, tan * tan + $t::one())
+            }
+
+            fn powi(self, n: i32) -> Self {
+                let x = self.value();
+                let d = if n == 0 { $t::zero() } else { $t::from_f64(n as f64) * x.powi(n - 1) };
+                self.chain(x.powi(n), d)
+            }
+
+            fn sqrt(self) -> Self {
+                let sqrt = self.value().sqrt();
+                self.chain(sqrt, $t::one() / ($t::from_f64(2.0) * sqrt))
+            }
+
+            fn cbrt(self) -> Self {
+                let cbrt = self.value().cbrt();
+                self.chain(cbrt, $t::one() / ($t::from_f64(3.0) * cbrt * cbrt))
+            }
+
+            fn powf(self, n: f64) -> Self {
+                let x = self.value();
+                let d = if n == 0.0 { $t::zero() } else { $t::from_f64(n) * x.powf(n - 1.0) };
+                self.chain(x.powf(n), d)
+            }
+
+            fn pow(self, y: Self) -> Self {
+                let (x, n) = (self.value(), y.value());
+                let pow = x.pow(n);
+                let dfdx = if n.is_zero() { $t::zero() } else { n * x.pow(n - $t::one()) };
+                self.chain2(y, pow, dfdx, pow * x.ln())
+            }
+
+            fn exp2(self) -> Self {
+                let exp2 = self.value().exp2();
+                self.chain(exp2, exp2 * $t::from_f64(std::f64::consts::LN_2))
+            }
+
+            fn exp_m1(self) -> Self {
+                let x = self.value();
+                self.chain(x.exp_m1(), x.exp())
+            }
+
+            fn ln_1p(self) -> Self {
+                let x = self.value();
+                self.chain(x.ln_1p(), $t::one() / (x + $t::one()))
+            }
+
+            fn log2(self) -> Self {
+                let x = self.value();
+                self.chain(x.log2(), $t::one() / (x * $t::from_f64(std::f64::consts::LN_2)))
+            }
+
+            fn log10(self) -> Self {
+                let x = self.value();
+                self.chain(x.log10(), $t::one() / (x * $t::from_f64(std::f64::consts::LN_10)))
+            }
+
+            fn log(self, base: f64) -> Self {
+                let x = self.value();
+                self.chain(x.log(base), $t::one() / (x * $t::from_f64(base.ln())))
+            }
+
+            fn asin(self) -> Self {
+                let x = self.value();
+                self.chain(x.asin(), $t::one() / ($t::one() - x * x).sqrt())
+            }
+
+            fn acos(self) -> Self {
+                let x = self.value();
+                self.chain(x.acos(), -$t::one() / ($t::one() - x * x).sqrt())
+            }
+
+            fn atan(self) -> Self {
+                let x = self.value();
+                self.chain(x.atan(), $t::one() / (x * x + $t::one()))
+            }
+
+            fn atan2(self, other: Self) -> Self {
+                let (y, x) = (self.value(), other.value());
+                let r2 = x * x + y * y;
+                self.chain2(other, y.atan2(x), x / r2, -y / r2)
+            }
+
+            fn sinh(self) -> Self {
+                let x = self.value();
+                self.chain(x.sinh(), x.cosh())
+            }
+
+            fn cosh(self) -> Self {
+                let x = self.value();
+                self.chain(x.cosh(), x.sinh())
+            }
+
+            fn tanh(self) -> Self {
+                let tanh = self.value().tanh();
+                self.chain(tanh, $t::one() - tanh * tanh)
+            }
+
+            fn asinh(self) -> Self {
+                let x = self.value();
+                self.chain(x.asinh(), $t::one() / (x * x + $t::one()).sqrt())
+            }
+
+            fn acosh(self) -> Self {
+                let x = self.value();
+                self.chain(x.acosh(), $t::one() / (x * x - $t::one()).sqrt())
+            }
+
+            fn atanh(self) -> Self {
+                let x = self.value();
+                self.chain(x.atanh(), $t::one() / ($t::one() - x * x))
+            }
+
+            fn erf(self) -> Self {
+                let x = self.value();
+                let d = $t::from_f64(std::f64::consts::FRAC_2_SQRT_PI) * (-(x * x)).exp();
+                self.chain($crate::ops::Ops::erf(x), d)
+            }
+
+            fn hypot(self, other: Self) -> Self {
+                let (x, y) = (self.value(), other.value());
+                let hypot = x.hypot(y);
+                self.chain2(other, hypot, x / hypot, y / hypot)
+            }
+
+            fn abs(self) -> Self {
+                let x = self.value();
+                self.chain(x.abs(), x.signum())
+            }
+
+            fn signum(self) -> Self {
+                let x = self.value();
+                self.chain(x.signum(), $t::zero())
+            }
+        }
+    };
+}
+
+pub(crate) use impl_ops_by_chain_rule;
+
+/// Applies the partial `a` to the tangent entry `d`. A zero `d` stays zero
+/// instead of becoming `0 · ∞ = NaN`: the entry belongs to an input the
+/// operand doesn't depend on, whatever the partial.
+pub(crate) fn scale<T: Real>(a: T, d: T) -> T {
+    if d.is_zero() {
+        d
+    } else {
+        a * d
+    }
+}
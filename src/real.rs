@@ -0,0 +1,140 @@
+use crate::ops::Ops;
+use crate::scalar::Scalar;
+use crate::special;
+
+/// Scalar field a [`Dual`](crate::Dual) can be built over: a [`Scalar`]
+/// that mixes with itself and is `Copy`.
+///
+/// Implemented for `f32`, `f64` and every `Copy` dual type, so duals nest;
+/// user types get it by implementing `Scalar<Self>`.
+pub trait Real: Copy + Scalar<Self> {}
+
+impl<T: Copy + Scalar<T>> Real for T {}
+
+macro_rules! impl_ops {
+    ($($t:ident),*) => {$(
+        impl Ops for $t {
+            fn exp(self) -> Self {
+                $t::exp(self)
+            }
+
+            fn ln(self) -> Self {
+                $t::ln(self)
+            }
+
+            fn sin(self) -> Self {
+                $t::sin(self)
+            }
+
+            fn cos(self) -> Self {
+                $t::cos(self)
+            }
+
+            fn tan(self) -> Self {
+                $t::tan(self)
+            }
+
+            fn powi(self, n: i32) -> Self {
+                $t::powi(self, n)
+            }
+
+            fn sqrt(self) -> Self {
+                $t::sqrt(self)
+            }
+
+            fn cbrt(self) -> Self {
+                $t::cbrt(self)
+            }
+
+            fn powf(self, n: f64) -> Self {
+                $t::powf(self, n as $t)
+            }
+
+            fn pow(self, y: Self) -> Self {
+                $t::powf(self, y)
+            }
+
+            fn exp2(self) -> Self {
+                $t::exp2(self)
+            }
+
+            fn exp_m1(self) -> Self {
+                $t::exp_m1(self)
+            }
+
+            fn ln_1p(self) -> Self {
+                $t::ln_1p(self)
+            }
+
+            fn log2(self) -> Self {
+                $t::log2(self)
+            }
+
+            fn log10(self) -> Self {
+                $t::log10(self)
+            }
+
+            fn log(self, base: f64) -> Self {
+                $t::log(self, base as $t)
+            }
+
+            fn asin(self) -> Self {
+                $t::asin(self)
+            }
+
+            fn acos(self) -> Self {
+                $t::acos(self)
+            }
+
+            fn atan(self) -> Self {
+                $t::atan(self)
+            }
+
+            fn atan2(self, other: Self) -> Self {
+                $t::atan2(self, other)
+            }
+
+            fn sinh(self) -> Self {
+                $t::sinh(self)
+            }
+
+            fn cosh(self) -> Self {
+                $t::cosh(self)
+            }
+
+            fn tanh(self) -> Self {
+                $t::tanh(self)
+            }
+
+            fn asinh(self) -> Self {
+                $t::asinh(self)
+            }
+
+            fn acosh(self) -> Self {
+                $t::acosh(self)
+            }
+
+            fn atanh(self) -> Self {
+                $t::atanh(self)
+            }
+
+            fn erf(self) -> Self {
+                special::erf(self as f64) as $t
+            }
+
+            fn hypot(self, other: Self) -> Self {
+                $t::hypot(self, other)
+            }
+
+            fn abs(self) -> Self {
+                $t::abs(self)
+            }
+
+            fn signum(self) -> Self {
+                $t::signum(self)
+            }
+        }
+    )*};
+}
+
+impl_ops!(f32, f64);
@@ -0,0 +1,335 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::ops::{Neg, Add, Sub, Mul, Div};
+
+use crate::activation::Sigmoid;
+use crate::ops::impl_ops_by_chain_rule;
+use crate::scalar::Scalar;
+
+/// A Wengert list recording every operation on the [`Var`]s created from
+/// it, for reverse-mode differentiation.
+///
+/// A tape only grows; use a fresh one per evaluation.
+#[derive(Debug)]
+pub struct Tape {
+    /// Unique for the life of the process, unlike the tape's address.
+    id: usize,
+    nodes: RefCell<Vec<Node>>,
+}
+
+static NEXT_TAPE_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// One recorded operation: the indices of its operands and the local
+/// partial derivative with respect to each.
+#[derive(Debug, Copy, Clone)]
+struct Node {
+    parents: [Option<(usize, f64)>; 2],
+}
+
+impl Default for Tape {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tape {
+    /// Creates an empty tape.
+    pub fn new() -> Self {
+        Self {
+            id: NEXT_TAPE_ID.fetch_add(1, Ordering::Relaxed),
+            nodes: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Creates a leaf variable on this tape.
+    pub fn var(&self, x: f64) -> Var<'_> {
+        Var {
+            tape: Some(self),
+            index: self.push([None, None]),
+            x,
+        }
+    }
+
+    /// Creates one leaf variable per input.
+    pub fn vars(&self, xs: &[f64]) -> Vec<Var<'_>> {
+        xs.iter().map(|&x| self.var(x)).collect()
+    }
+
+    /// Returns the number of recorded nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.borrow().len()
+    }
+
+    /// Returns `true` if nothing has been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&self, parents: [Option<(usize, f64)>; 2]) -> usize {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(Node { parents });
+        nodes.len() - 1
+    }
+}
+
+/// A reverse-mode variable: a value plus its position on a [`Tape`].
+///
+/// Constants ([`Var::constant`]) live off the tape and cost nothing to
+/// record; operations between two taped variables require both to come
+/// from the same tape.
+#[derive(Debug, Copy, Clone)]
+pub struct Var<'t> {
+    tape: Option<&'t Tape>,
+    index: usize,
+    x: f64,
+}
+
+impl<'t> Var<'t> {
+    /// Creates a constant that isn't recorded on any tape.
+    pub fn constant(x: f64) -> Self {
+        Self {
+            tape: None,
+            index: 0,
+            x,
+        }
+    }
+
+    /// Returns the primal value.
+    pub fn value(&self) -> f64 {
+        self.x
+    }
+
+    /// Propagates adjoints from `self` back to every node on its tape.
+    pub fn backward(&self) -> Grad {
+        let Some(tape) = self.tape else {
+            return Grad {
+                tape: None,
+                adjoints: Vec::new(),
+            };
+        };
+        let nodes = tape.nodes.borrow();
+        let mut adjoints = vec![0.0; self.index + 1];
+        adjoints[self.index] = 1.0;
+        for i in (0..=self.index).rev() {
+            let adjoint = adjoints[i];
+            if adjoint == 0.0 {
+                continue;
+            }
+            for &(parent, partial) in nodes[i].parents.iter().flatten() {
+                adjoints[parent] += partial * adjoint;
+            }
+        }
+        Grad {
+            tape: Some(tape.id),
+            adjoints,
+        }
+    }
+
+    fn chain(self, fx: f64, dfx: f64) -> Self {
+        match self.tape {
+            Some(tape) => Self {
+                tape: Some(tape),
+                index: tape.push([Some((self.index, dfx)), None]),
+                x: fx,
+            },
+            None => Self::constant(fx),
+        }
+    }
+
+    fn chain2(self, other: Self, fx: f64, dfdx: f64, dfdy: f64) -> Self {
+        match (self.tape, other.tape) {
+            (Some(a), Some(b)) => {
+                assert!(
+                    std::ptr::eq(a, b),
+                    "Var operands are recorded on different tapes"
+                );
+                Self {
+                    tape: Some(a),
+                    index: a.push([Some((self.index, dfdx)), Some((other.index, dfdy))]),
+                    x: fx,
+                }
+            }
+            (Some(_), None) => self.chain(fx, dfdx),
+            (None, Some(_)) => other.chain(fx, dfdy),
+            (None, None) => Self::constant(fx),
+        }
+    }
+}
+
+/// Adjoints produced by [`Var::backward`].
+#[derive(Debug, Clone)]
+pub struct Grad {
+    /// The id of the tape the output was recorded on, which keeps `Grad`
+    /// `Send`; `None` for a constant output.
+    tape: Option<usize>,
+    adjoints: Vec<f64>,
+}
+
+impl Grad {
+    /// Returns the derivative of the output with respect to `v`. Zero for
+    /// constants and for variables the output doesn't depend on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` and the output are recorded on different tapes.
+    pub fn wrt(&self, v: &Var) -> f64 {
+        match (self.tape, v.tape) {
+            (Some(a), Some(b)) => {
+                assert!(a == b.id, "Var is recorded on a different tape than the output");
+                self.adjoints.get(v.index).copied().unwrap_or(0.0)
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the derivatives with respect to each of `vs`.
+    pub fn wrt_all(&self, vs: &[Var]) -> Vec<f64> {
+        vs.iter().map(|v| self.wrt(v)).collect()
+    }
+}
+
+impl<'t> Neg for Var<'t> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self.chain(-self.x, -1.0)
+    }
+}
+
+impl<'t> Add for Var<'t> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        self.chain2(rhs, self.x + rhs.x, 1.0, 1.0)
+    }
+}
+
+impl<'t> Sub for Var<'t> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self.chain2(rhs, self.x - rhs.x, 1.0, -1.0)
+    }
+}
+
+impl<'t> Mul for Var<'t> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.chain2(rhs, self.x * rhs.x, rhs.x, self.x)
+    }
+}
+
+impl<'t> Div for Var<'t> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let (x, y) = (self.x, rhs.x);
+        self.chain2(rhs, x / y, 1.0 / y, -x / (y * y))
+    }
+}
+
+impl<'t> Add<f64> for Var<'t> {
+    type Output = Self;
+    fn add(self, rhs: f64) -> Self {
+        self.chain(self.x + rhs, 1.0)
+    }
+}
+
+impl<'t> Sub<f64> for Var<'t> {
+    type Output = Self;
+    fn sub(self, rhs: f64) -> Self {
+        self.chain(self.x - rhs, 1.0)
+    }
+}
+
+impl<'t> Mul<f64> for Var<'t> {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        self.chain(self.x * rhs, rhs)
+    }
+}
+
+impl<'t> Div<f64> for Var<'t> {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        self.chain(self.x / rhs, 1.0 / rhs)
+    }
+}
+
+impl<'t> Add<Var<'t>> for f64 {
+    type Output = Var<'t>;
+    fn add(self, rhs: Var<'t>) -> Var<'t> {
+        rhs + self
+    }
+}
+
+impl<'t> Sub<Var<'t>> for f64 {
+    type Output = Var<'t>;
+    fn sub(self, rhs: Var<'t>) -> Var<'t> {
+        rhs.chain(self - rhs.x, -1.0)
+    }
+}
+
+impl<'t> Mul<Var<'t>> for f64 {
+    type Output = Var<'t>;
+    fn mul(self, rhs: Var<'t>) -> Var<'t> {
+        rhs * self
+    }
+}
+
+impl<'t> Div<Var<'t>> for f64 {
+    type Output = Var<'t>;
+    fn div(self, rhs: Var<'t>) -> Var<'t> {
+        let x = rhs.x;
+        rhs.chain(self / x, -self / (x * x))
+    }
+}
+
+impl<'t> Sigmoid<f64> for Var<'t> {
+    fn sigmoid(self) -> Self {
+        let s = self.x.sigmoid();
+        self.chain(s, s * (1.0 - s))
+    }
+}
+
+impl<'t> Scalar for Var<'t> {
+    fn zero() -> Self {
+        Self::constant(0.0)
+    }
+
+    fn one() -> Self {
+        Self::constant(1.0)
+    }
+
+    fn from_f64(x: f64) -> Self {
+        Self::constant(x)
+    }
+
+    fn to_f64(&self) -> f64 {
+        self.x
+    }
+
+    fn is_zero(&self) -> bool {
+        self.tape.is_none() && self.x == 0.0
+    }
+}
+
+impl<'t> Scalar<Var<'t>> for Var<'t> {
+    fn zero() -> Self {
+        <Self as Scalar>::zero()
+    }
+
+    fn one() -> Self {
+        <Self as Scalar>::one()
+    }
+
+    fn from_f64(x: f64) -> Self {
+        <Self as Scalar>::from_f64(x)
+    }
+
+    fn to_f64(&self) -> f64 {
+        <Self as Scalar>::to_f64(self)
+    }
+
+    fn is_zero(&self) -> bool {
+        <Self as Scalar>::is_zero(self)
+    }
+}
+
+impl_ops_by_chain_rule!(['t] Var<'t>, f64);
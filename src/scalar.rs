@@ -0,0 +1,181 @@
+use std::ops::{Neg, Add, Sub, Mul, Div};
+
+use crate::ops::Ops;
+
+/// A number type that model code can be written against once, whether it
+/// runs on plain floats, a dual type, [`Var`](crate::Var) or a tracer.
+///
+/// `T` is the plain scalar it mixes with, so literals work on the right
+/// of any operator: `x * 2.0 + 1.0`. Every implementor also supports the
+/// literal on the left, but a generic `S: Scalar` only assumes bounds on
+/// `S` itself; write `S::from_f64(2.0) - x`, or add the bound
+/// `f64: Sub<S, Output = S>`, to use that form generically.
+///
+/// Every type also mixes with itself, as `Scalar<Self>`. Code that never
+/// touches a plain scalar, like [`softmax`](crate::softmax), takes that
+/// bound, so it accepts `f32`- and `f64`-based types alike.
+///
+/// ```
+/// use dual::{Dual, Ops, Scalar};
+///
+/// fn model<S: Scalar>(x: S) -> S {
+///     x.clone().sin() * 3.0 + x.clone() * x - S::one()
+/// }
+///
+/// assert_eq!(model(0.5), 0.5f64.sin() * 3.0 + 0.25 - 1.0);
+/// assert_eq!(model(Dual::variable(0.5)).deriv(), 0.5f64.cos() * 3.0 + 1.0);
+/// ```
+pub trait Scalar<T = f64>: Clone
+    + Ops
+    + Neg<Output=Self>
+    + Add<Output=Self>
+    + Sub<Output=Self>
+    + Mul<Output=Self>
+    + Div<Output=Self>
+    + Add<T, Output=Self>
+    + Sub<T, Output=Self>
+    + Mul<T, Output=Self>
+    + Div<T, Output=Self> {
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn from_f64(x: f64) -> Self;
+
+    /// Returns the primal value as `f64`, dropping any derivative parts.
+    /// Tracers carry no value and return NaN.
+    fn to_f64(&self) -> f64;
+
+    /// Returns `true` if `self` is exactly zero, derivative parts included.
+    /// Taped [`Var`](crate::Var)s and tracers are never known to be zero.
+    fn is_zero(&self) -> bool;
+}
+
+macro_rules! impl_scalar {
+    ($($t:ident),*) => {$(
+        impl Scalar<$t> for $t {
+            fn zero() -> Self {
+                0.0
+            }
+
+            fn one() -> Self {
+                1.0
+            }
+
+            fn from_f64(x: f64) -> Self {
+                x as $t
+            }
+
+            fn to_f64(&self) -> f64 {
+                *self as f64
+            }
+
+            fn is_zero(&self) -> bool {
+                *self == 0.0
+            }
+        }
+    )*};
+}
+
+impl_scalar!(f32, f64);
+
+/// Implements the plumbing shared by the forward-mode types from their
+/// generic parameters (each followed by a comma), their name and the
+/// parameters it takes ahead of the scalar `T`:
+///
+/// - `Scalar<Self>`, forwarding to the type's own `Scalar<T>`, which makes
+///   the type a [`Real`](crate::Real) so it can be nested;
+/// - `f32` and `f64` on the left of `+`, `-` and `*` by commuting, and of
+///   `/` through the given `|lhs, rhs|` quotient;
+/// - [`Sigmoid`](crate::Sigmoid) over either float: `first_order` and
+///   `second_order` apply `σ' = σ (1 - σ)` and `σ'' = σ' (1 - 2σ)` through
+///   the type's `chain` helper, `provided` keeps the trait's default.
+///
+/// `impl<T> Div<Dual<T>> for T` is rejected by the orphan rule, so the
+/// scalar-on-the-left impls are spelled out per primitive. Each float only
+/// mixes with types of its own width, on either side, and there are no
+/// mixed-width operators on purpose: `Dual<f64> * f32` or `f32 * Dual<f64>`
+/// as well would leave `x * 2.0` with two candidate impls, and a method
+/// called on the result would no longer infer.
+macro_rules! impl_mixed_scalars {
+    ($gen:tt $name:ident $params:tt, |$a:ident, $b:ident| $div:expr, sigmoid: $sigmoid:ident) => {
+        $crate::scalar::impl_mixed_scalars!(@self $gen $name $params);
+        $crate::scalar::impl_mixed_scalars!(@lhs $gen $name $params f32, |$a, $b| $div, $sigmoid);
+        $crate::scalar::impl_mixed_scalars!(@lhs $gen $name $params f64, |$a, $b| $div, $sigmoid);
+    };
+    (@self [$($gen:tt)*] $name:ident [$($p:ident),*]) => {
+        impl<$($gen)* T: $crate::real::Real> $crate::scalar::Scalar<$name<$($p,)* T>> for $name<$($p,)* T> {
+            fn zero() -> Self {
+                <Self as $crate::scalar::Scalar<T>>::zero()
+            }
+
+            fn one() -> Self {
+                <Self as $crate::scalar::Scalar<T>>::one()
+            }
+
+            fn from_f64(x: f64) -> Self {
+                <Self as $crate::scalar::Scalar<T>>::from_f64(x)
+            }
+
+            fn to_f64(&self) -> f64 {
+                <Self as $crate::scalar::Scalar<T>>::to_f64(self)
+            }
+
+            fn is_zero(&self) -> bool {
+                <Self as $crate::scalar::Scalar<T>>::is_zero(self)
+            }
+        }
+    };
+    (@lhs [$($gen:tt)*] $name:ident [$($p:ident),*] $t:ident, |$a:ident, $b:ident| $div:expr, $sigmoid:ident) => {
+        impl<$($gen)*> std::ops::Add<$name<$($p,)* $t>> for $t {
+            type Output = $name<$($p,)* $t>;
+
+            fn add(self, rhs: $name<$($p,)* $t>) -> $name<$($p,)* $t> {
+                rhs + self
+            }
+        }
+
+        impl<$($gen)*> std::ops::Sub<$name<$($p,)* $t>> for $t {
+            type Output = $name<$($p,)* $t>;
+
+            fn sub(self, rhs: $name<$($p,)* $t>) -> $name<$($p,)* $t> {
+                -rhs + self
+            }
+        }
+
+        impl<$($gen)*> std::ops::Mul<$name<$($p,)* $t>> for $t {
+            type Output = $name<$($p,)* $t>;
+
+            fn mul(self, rhs: $name<$($p,)* $t>) -> $name<$($p,)* $t> {
+                rhs * self
+            }
+        }
+
+        impl<$($gen)*> std::ops::Div<$name<$($p,)* $t>> for $t {
+            type Output = $name<$($p,)* $t>;
+
+            fn div(self, rhs: $name<$($p,)* $t>) -> $name<$($p,)* $t> {
+                let ($a, $b) = (self, rhs);
+                $div
+            }
+        }
+
+        impl<$($gen)*> $crate::activation::Sigmoid<$t> for $name<$($p,)* $t> {
+            $crate::scalar::impl_mixed_scalars!(@sigmoid $sigmoid);
+        }
+    };
+    (@sigmoid first_order) => {
+        fn sigmoid(self) -> Self {
+            let s = $crate::activation::Sigmoid::sigmoid(self.x);
+            self.chain(s, s * (1.0 - s))
+        }
+    };
+    (@sigmoid second_order) => {
+        fn sigmoid(self) -> Self {
+            let s = $crate::activation::Sigmoid::sigmoid(self.x);
+            let ds = s * (1.0 - s);
+            self.chain(s, ds, ds * (1.0 - 2.0 * s))
+        }
+    };
+    (@sigmoid provided) => {};
+}
+
+pub(crate) use impl_mixed_scalars;
@@ -0,0 +1,85 @@
+use crate::scalar::Scalar;
+
+/// The largest primal value, or zero when it isn't finite.
+///
+/// The shift is a constant: `logsumexp(x) = m + logsumexp(x - m)` for any
+/// `m`, so it changes no derivative. Falling back to zero keeps an empty or
+/// all `-inf` input at `-inf` without computing `-inf - -inf`; a `+inf`
+/// maximum is left to [`saturated`].
+fn shift<S: Scalar<S>>(x: &[S]) -> S {
+    let m = x.iter().map(|xi| xi.to_f64()).fold(f64::NEG_INFINITY, f64::max);
+    S::from_f64(if m.is_finite() { m } else { 0.0 })
+}
+
+/// Which entries are `+inf`, if any is. In that limit the `+inf` entries
+/// share all of the probability mass equally and the rest get none, so the
+/// results are constants.
+fn saturated<S: Scalar<S>>(x: &[S]) -> Option<(Vec<bool>, f64)> {
+    let top: Vec<bool> = x.iter().map(|xi| xi.to_f64() == f64::INFINITY).collect();
+    let k = top.iter().filter(|&&t| t).count();
+    (k > 0).then_some((top, k as f64))
+}
+
+/// `ln Σ exp(xᵢ)`, shifted by the maximum so that no term overflows.
+///
+/// The derivative with respect to `xᵢ` is `softmax(x)ᵢ`. An empty slice
+/// gives `-inf`.
+pub fn logsumexp<S: Scalar<S>>(x: &[S]) -> S {
+    let m = shift(x);
+    let sum = x
+        .iter()
+        .map(|xi| (xi.clone() - m.clone()).exp())
+        .fold(S::zero(), |acc, e| acc + e);
+    sum.ln() + m
+}
+
+/// `exp(xᵢ) / Σ exp(xⱼ)`, shifted by the maximum so that no term
+/// overflows.
+///
+/// If some `xᵢ` are `+inf`, they split the mass equally: `softmax([inf, 1])`
+/// is `[1, 0]`.
+pub fn softmax<S: Scalar<S>>(x: &[S]) -> Vec<S> {
+    if let Some((top, k)) = saturated(x) {
+        return top.into_iter().map(|t| S::from_f64(if t { 1.0 / k } else { 0.0 })).collect();
+    }
+    let m = shift(x);
+    let e: Vec<S> = x.iter().map(|xi| (xi.clone() - m.clone()).exp()).collect();
+    let sum = e.iter().cloned().fold(S::zero(), |acc, e| acc + e);
+    e.into_iter().map(|ei| ei / sum.clone()).collect()
+}
+
+/// `xᵢ - logsumexp(x)`, finite wherever `x` is, unlike `ln(softmax(x))`.
+///
+/// With `+inf` entries this is the logarithm of the limit in [`softmax`].
+pub fn log_softmax<S: Scalar<S>>(x: &[S]) -> Vec<S> {
+    if let Some((top, k)) = saturated(x) {
+        let log = |t| if t { -k.ln() } else { f64::NEG_INFINITY };
+        return top.into_iter().map(|t| S::from_f64(log(t))).collect();
+    }
+    let lse = logsumexp(x);
+    x.iter().map(|xi| xi.clone() - lse.clone()).collect()
+}
+
+/// Cross-entropy of unnormalized `logits` against the class `target`:
+/// `-log_softmax(logits)[target]`.
+///
+/// # Panics
+///
+/// Panics if `target` is out of bounds.
+pub fn cross_entropy<S: Scalar<S>>(logits: &[S], target: usize) -> S {
+    let logit = logits[target].clone();
+    if let Some((top, k)) = saturated(logits) {
+        return S::from_f64(if top[target] { k.ln() } else { f64::INFINITY });
+    }
+    logsumexp(logits) - logit
+}
+
+/// Negative log-likelihood of the class `target` given `log_probs`, e.g.
+/// the output of [`log_softmax`]: `-log_probs[target]`.
+///
+/// # Panics
+///
+/// Panics if `target` is out of bounds.
+pub fn nll<S: Scalar<S>>(log_probs: &[S], target: usize) -> S {
+    -log_probs[target].clone()
+}
@@ -0,0 +1,254 @@
+use std::cmp::Ordering;
+use std::ops::{Neg, Add, Sub, Mul, Div};
+
+use crate::ops::{impl_ops_by_chain_rule, scale};
+use crate::real::Real;
+use crate::scalar::{impl_mixed_scalars, Scalar};
+
+/// A dual number with a sparse gradient stored as `(index, partial)`
+/// pairs sorted by input index.
+///
+/// Binary operations merge the two index lists, so the cost of each step
+/// is proportional to the number of inputs the operands actually depend
+/// on, not to the total number of inputs. Entries are structural: a
+/// partial that cancels to zero keeps its slot, so the index list is the
+/// dependency set of the value.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(from = "SparseParts<T>", bound(deserialize = "T: Real + serde::Deserialize<'de>"))
+)]
+pub struct SparseDual<T = f64> {
+    x: T,
+    dx: Vec<(usize, T)>,
+}
+
+/// The serialized form of a [`SparseDual`], normalized by
+/// [`SparseDual::new`] on the way in.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SparseParts<T> {
+    x: T,
+    dx: Vec<(usize, T)>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Real> From<SparseParts<T>> for SparseDual<T> {
+    fn from(parts: SparseParts<T>) -> Self {
+        Self::new(parts.x, parts.dx)
+    }
+}
+
+impl<T: Real> SparseDual<T> {
+    /// Creates a sparse dual number from `(index, partial)` pairs.
+    ///
+    /// The pairs are sorted by index; repeated indices are summed.
+    pub fn new(x: T, mut dx: Vec<(usize, T)>) -> Self {
+        dx.sort_by_key(|&(i, _)| i);
+        dx.dedup_by(|(i, d), (j, acc)| {
+            let same = i == j;
+            if same {
+                *acc = *acc + *d;
+            }
+            same
+        });
+        Self { x, dx }
+    }
+
+    /// Creates the `i`-th independent variable, i.e. `x + 1 εᵢ`.
+    pub fn variable(x: T, i: usize) -> Self {
+        Self {
+            x,
+            dx: vec![(i, T::one())],
+        }
+    }
+
+    /// Creates a constant with an empty gradient.
+    pub fn constant(x: T) -> Self {
+        Self { x, dx: Vec::new() }
+    }
+
+    /// Seeds every input at once: the `i`-th result is `xs[i] + 1 εᵢ`.
+    pub fn variables(xs: &[T]) -> Vec<Self> {
+        xs.iter()
+            .enumerate()
+            .map(|(i, &x)| Self::variable(x, i))
+            .collect()
+    }
+
+    /// Returns the primal value.
+    pub fn value(&self) -> T {
+        self.x
+    }
+
+    /// Returns the non-structurally-zero partials, sorted by input index.
+    pub fn deriv(&self) -> &[(usize, T)] {
+        &self.dx
+    }
+
+    /// Returns the partial derivative with respect to input `i`.
+    pub fn partial(&self, i: usize) -> T {
+        match self.dx.binary_search_by_key(&i, |&(j, _)| j) {
+            Ok(k) => self.dx[k].1,
+            Err(_) => T::zero(),
+        }
+    }
+
+    fn chain(mut self, fx: T, dfx: T) -> Self {
+        self.dx.iter_mut().for_each(|(_, d)| *d = scale(dfx, *d));
+        Self { x: fx, dx: self.dx }
+    }
+
+    fn chain2(self, other: Self, fx: T, dfdx: T, dfdy: T) -> Self {
+        Self {
+            x: fx,
+            dx: merge(dfdx, self.dx, dfdy, other.dx),
+        }
+    }
+}
+
+/// Computes `a dx + b dy` by merging the two sorted index lists.
+fn merge<T: Real>(a: T, dx: Vec<(usize, T)>, b: T, dy: Vec<(usize, T)>) -> Vec<(usize, T)> {
+    let mut out = Vec::with_capacity(dx.len() + dy.len());
+    let mut dx = dx.into_iter().peekable();
+    let mut dy = dy.into_iter().peekable();
+    loop {
+        let next = match (dx.peek(), dy.peek()) {
+            (Some(&(i, p)), Some(&(j, q))) => match i.cmp(&j) {
+                Ordering::Less => {
+                    dx.next();
+                    (i, scale(a, p))
+                }
+                Ordering::Greater => {
+                    dy.next();
+                    (j, scale(b, q))
+                }
+                Ordering::Equal => {
+                    dx.next();
+                    dy.next();
+                    (i, scale(a, p) + scale(b, q))
+                }
+            },
+            (Some(&(i, p)), None) => {
+                dx.next();
+                (i, scale(a, p))
+            }
+            (None, Some(&(j, q))) => {
+                dy.next();
+                (j, scale(b, q))
+            }
+            (None, None) => return out,
+        };
+        out.push(next);
+    }
+}
+
+impl<T: Real> Neg for SparseDual<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        let x = -self.x;
+        self.chain(x, -T::one())
+    }
+}
+
+impl<T: Real> Add for SparseDual<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        let x = self.x + rhs.x;
+        self.chain2(rhs, x, T::one(), T::one())
+    }
+}
+
+impl<T: Real> Sub for SparseDual<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        let x = self.x - rhs.x;
+        self.chain2(rhs, x, T::one(), -T::one())
+    }
+}
+
+impl<T: Real> Mul for SparseDual<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let (x, y) = (self.x, rhs.x);
+        self.chain2(rhs, x * y, y, x)
+    }
+}
+
+impl<T: Real> Div for SparseDual<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let (x, y) = (self.x, rhs.x);
+        self.chain2(rhs, x / y, T::one() / y, -x / (y * y))
+    }
+}
+
+impl<T: Real> Add<T> for SparseDual<T> {
+    type Output = Self;
+    fn add(self, rhs: T) -> Self {
+        Self {
+            x: self.x + rhs,
+            dx: self.dx,
+        }
+    }
+}
+
+impl<T: Real> Sub<T> for SparseDual<T> {
+    type Output = Self;
+    fn sub(self, rhs: T) -> Self {
+        Self {
+            x: self.x - rhs,
+            dx: self.dx,
+        }
+    }
+}
+
+impl<T: Real> Mul<T> for SparseDual<T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        let x = self.x * rhs;
+        self.chain(x, rhs)
+    }
+}
+
+impl<T: Real> Div<T> for SparseDual<T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self {
+        let x = self.x / rhs;
+        self.chain(x, T::one() / rhs)
+    }
+}
+
+impl<T: Real> Scalar<T> for SparseDual<T> {
+    fn zero() -> Self {
+        Self::constant(T::zero())
+    }
+
+    fn one() -> Self {
+        Self::constant(T::one())
+    }
+
+    fn from_f64(x: f64) -> Self {
+        Self::constant(T::from_f64(x))
+    }
+
+    fn to_f64(&self) -> f64 {
+        self.x.to_f64()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.x.is_zero() && self.dx.iter().all(|(_, d)| d.is_zero())
+    }
+}
+
+impl_mixed_scalars!(
+    [] SparseDual [],
+    |lhs, rhs| {
+        let x = rhs.x;
+        rhs.chain(lhs / x, -lhs / (x * x))
+    },
+    sigmoid: first_order
+);
+
+impl_ops_by_chain_rule!([T: Real] SparseDual<T>, T);
@@ -0,0 +1,343 @@
+use crate::api::{Function, VectorFunction};
+use crate::dual_n::DualN;
+use crate::matrix::Matrix;
+use crate::tracer::{HessianTape, TraceTape};
+
+/// Number of colors (compressed columns) seeded per evaluation.
+const LANES: usize = 8;
+
+/// The nonzero structure of an `m × n` matrix in compressed sparse row
+/// form: row `i` has its column indices, sorted, at
+/// `col_idx[row_ptr[i]..row_ptr[i + 1]]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SparsityParts"))]
+pub struct Sparsity {
+    rows: usize,
+    cols: usize,
+    row_ptr: Vec<usize>,
+    col_idx: Vec<usize>,
+}
+
+/// The serialized form of a [`Sparsity`], checked to be well-formed CSR on
+/// the way in.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SparsityParts {
+    rows: usize,
+    cols: usize,
+    row_ptr: Vec<usize>,
+    col_idx: Vec<usize>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SparsityParts> for Sparsity {
+    type Error = &'static str;
+
+    fn try_from(parts: SparsityParts) -> Result<Self, Self::Error> {
+        let SparsityParts { rows, cols, row_ptr, col_idx } = parts;
+        if row_ptr.len().checked_sub(1) != Some(rows) || row_ptr[0] != 0 || row_ptr[rows] != col_idx.len() {
+            return Err("Sparsity row pointers don't match the shape");
+        }
+        for w in row_ptr.windows(2) {
+            let row = col_idx.get(w[0]..w[1]).ok_or("Sparsity row pointers aren't sorted")?;
+            if !row.windows(2).all(|c| c[0] < c[1]) || row.last().is_some_and(|&j| j >= cols) {
+                return Err("Sparsity column indices aren't sorted and in range");
+            }
+        }
+        Ok(Self { rows, cols, row_ptr, col_idx })
+    }
+}
+
+impl Sparsity {
+    /// Builds a pattern from the column indices of each row, given in any
+    /// order; duplicates are merged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a column index is out of range.
+    pub fn from_rows(cols: usize, rows: &[Vec<usize>]) -> Self {
+        let mut row_ptr = Vec::with_capacity(rows.len() + 1);
+        let mut col_idx = Vec::new();
+        row_ptr.push(0);
+        for row in rows {
+            assert!(row.iter().all(|&j| j < cols), "Sparsity column index out of range");
+            let mut row = row.clone();
+            row.sort_unstable();
+            row.dedup();
+            col_idx.extend(row);
+            row_ptr.push(col_idx.len());
+        }
+        Self {
+            rows: rows.len(),
+            cols,
+            row_ptr,
+            col_idx,
+        }
+    }
+
+    /// Returns the number of rows, `m`.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns, `n`.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Returns the number of structural nonzeros.
+    pub fn nnz(&self) -> usize {
+        self.col_idx.len()
+    }
+
+    /// Returns the column indices of row `i`.
+    pub fn row(&self, i: usize) -> &[usize] {
+        &self.col_idx[self.row_ptr[i]..self.row_ptr[i + 1]]
+    }
+
+    /// Returns the CSR row offsets: `rows() + 1` entries, starting at zero
+    /// and ending at [`nnz`](Sparsity::nnz).
+    pub fn row_ptr(&self) -> &[usize] {
+        &self.row_ptr
+    }
+
+    /// Returns the column indices in CSR order: row by row, sorted within
+    /// each row.
+    pub fn col_idx(&self) -> &[usize] {
+        &self.col_idx
+    }
+
+    /// Greedy distance-2 coloring of the columns: two columns that share a
+    /// row never get the same color, so each row sees every color at most
+    /// once. Columns are visited in order of decreasing nonzero count.
+    ///
+    /// Returns the color of every column; colors are `0..count`.
+    pub fn color_columns(&self) -> Vec<usize> {
+        let mut col_rows = vec![Vec::new(); self.cols];
+        for i in 0..self.rows {
+            for &j in self.row(i) {
+                col_rows[j].push(i);
+            }
+        }
+        let mut order: Vec<usize> = (0..self.cols).collect();
+        order.sort_by_key(|&j| std::cmp::Reverse(col_rows[j].len()));
+
+        const UNCOLORED: usize = usize::MAX;
+        let mut colors = vec![UNCOLORED; self.cols];
+        // `forbidden[c] == j` marks color `c` as taken by a neighbour of `j`.
+        let mut forbidden: Vec<usize> = Vec::new();
+        for &j in &order {
+            for &i in &col_rows[j] {
+                for &k in self.row(i) {
+                    if colors[k] != UNCOLORED {
+                        forbidden[colors[k]] = j;
+                    }
+                }
+            }
+            let color = (0..forbidden.len())
+                .find(|&c| forbidden[c] != j)
+                .unwrap_or(forbidden.len());
+            if color == forbidden.len() {
+                forbidden.push(UNCOLORED);
+            }
+            colors[j] = color;
+        }
+        colors
+    }
+}
+
+/// Detects the Jacobian sparsity pattern of `f: ℝⁿ → ℝᵐ` by running it
+/// once on tracers that record only which inputs each value depends on.
+pub fn jacobian_sparsity<F: VectorFunction>(f: &F, n: usize) -> Sparsity {
+    let tape = TraceTape::new(n);
+    let rows: Vec<Vec<usize>> = f.eval(&tape.inputs()).iter().map(|y| tape.deps(y)).collect();
+    Sparsity::from_rows(n, &rows)
+}
+
+/// Detects the Hessian sparsity pattern of `f: ℝⁿ → ℝ` by running it once
+/// on tracers that also record which input pairs each value couples.
+///
+/// The pattern is symmetric and includes both triangles.
+pub fn hessian_sparsity<F: Function>(f: &F, n: usize) -> Sparsity {
+    let tape = HessianTape::new(n);
+    let y = f.eval(&tape.inputs());
+    let mut rows = vec![Vec::new(); n];
+    for (i, j) in tape.hessian(&y) {
+        rows[i].push(j);
+        if i != j {
+            rows[j].push(i);
+        }
+    }
+    Sparsity::from_rows(n, &rows)
+}
+
+/// A sparse matrix in compressed sparse row form.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SparseMatrixParts"))]
+pub struct SparseMatrix {
+    pattern: Sparsity,
+    values: Vec<f64>,
+}
+
+/// The serialized form of a [`SparseMatrix`], checked to have one value
+/// per structural nonzero on the way in.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SparseMatrixParts {
+    pattern: Sparsity,
+    values: Vec<f64>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SparseMatrixParts> for SparseMatrix {
+    type Error = &'static str;
+
+    fn try_from(parts: SparseMatrixParts) -> Result<Self, Self::Error> {
+        if parts.values.len() != parts.pattern.nnz() {
+            return Err("SparseMatrix values don't match the pattern");
+        }
+        Ok(Self {
+            pattern: parts.pattern,
+            values: parts.values,
+        })
+    }
+}
+
+impl SparseMatrix {
+    /// Returns the sparsity pattern.
+    pub fn pattern(&self) -> &Sparsity {
+        &self.pattern
+    }
+
+    /// Returns the values, aligned with [`Sparsity::col_idx`].
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns the entry at `(i, j)`, zero outside the pattern.
+    pub fn get(&self, i: usize, j: usize) -> f64 {
+        let start = self.pattern.row_ptr[i];
+        match self.pattern.row(i).binary_search(&j) {
+            Ok(k) => self.values[start + k],
+            Err(_) => 0.0,
+        }
+    }
+
+    /// Returns the entries as `(row, column, value)` triplets, row by row.
+    pub fn to_coo(&self) -> Vec<(usize, usize, f64)> {
+        (0..self.pattern.rows)
+            .flat_map(|i| {
+                let start = self.pattern.row_ptr[i];
+                self.pattern
+                    .row(i)
+                    .iter()
+                    .enumerate()
+                    .map(move |(k, &j)| (i, j, self.values[start + k]))
+            })
+            .collect()
+    }
+
+    /// Returns a dense copy, with zeros outside the pattern.
+    pub fn to_dense(&self) -> Matrix {
+        let mut dense = Matrix::zeros(self.pattern.rows, self.pattern.cols);
+        for (i, j, v) in self.to_coo() {
+            dense[(i, j)] = v;
+        }
+        dense
+    }
+}
+
+/// A reusable plan for sparse Jacobians of one function: its sparsity
+/// pattern and a column coloring, both computed once.
+///
+/// Each evaluation seeds all columns of one color together, eight colors
+/// per pass, so the cost is `⌈colors / 8⌉` evaluations instead of
+/// `n`.
+#[derive(Debug, Clone)]
+pub struct SparseJacobian {
+    pattern: Sparsity,
+    colors: Vec<usize>,
+    color_count: usize,
+}
+
+impl SparseJacobian {
+    /// Detects the pattern of `f` on `n` inputs and colors its columns.
+    pub fn new<F: VectorFunction>(f: &F, n: usize) -> Self {
+        Self::from_pattern(jacobian_sparsity(f, n))
+    }
+
+    /// Colors the columns of a pattern known in advance.
+    pub fn from_pattern(pattern: Sparsity) -> Self {
+        let colors = pattern.color_columns();
+        let color_count = colors.iter().map(|&c| c + 1).max().unwrap_or(0);
+        Self {
+            pattern,
+            colors,
+            color_count,
+        }
+    }
+
+    /// Returns the Jacobian's sparsity pattern.
+    pub fn pattern(&self) -> &Sparsity {
+        &self.pattern
+    }
+
+    /// Returns the color of every column.
+    pub fn colors(&self) -> &[usize] {
+        &self.colors
+    }
+
+    /// Returns the number of function evaluations per Jacobian.
+    pub fn passes(&self) -> usize {
+        self.color_count.div_ceil(LANES)
+    }
+
+    /// Evaluates the Jacobian of `f` at `x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` or the output of `f` doesn't match the pattern's shape.
+    pub fn eval<F: VectorFunction>(&self, f: &F, x: &[f64]) -> SparseMatrix {
+        assert_eq!(x.len(), self.pattern.cols, "SparseJacobian: wrong number of inputs");
+        let mut values = vec![0.0; self.pattern.nnz()];
+        for pass in 0..self.passes() {
+            let first = pass * LANES;
+            let inputs: Vec<DualN<LANES>> = x
+                .iter()
+                .zip(&self.colors)
+                .map(|(&xj, &c)| {
+                    let mut dx = [0.0; LANES];
+                    if (first..first + LANES).contains(&c) {
+                        dx[c - first] = 1.0;
+                    }
+                    DualN::new(xj, dx)
+                })
+                .collect();
+            let ys = f.eval(&inputs);
+            assert_eq!(ys.len(), self.pattern.rows, "SparseJacobian: wrong number of outputs");
+            // Within a row every color appears at most once, so the
+            // compressed entry for a column's color is that column's entry.
+            for (i, y) in ys.iter().enumerate() {
+                let start = self.pattern.row_ptr[i];
+                for (k, &j) in self.pattern.row(i).iter().enumerate() {
+                    let c = self.colors[j];
+                    if (first..first + LANES).contains(&c) {
+                        values[start + k] = y.deriv()[c - first];
+                    }
+                }
+            }
+        }
+        SparseMatrix {
+            pattern: self.pattern.clone(),
+            values,
+        }
+    }
+}
+
+/// Computes the Jacobian of `f` at `x` with a fresh [`SparseJacobian`]
+/// plan. Build the plan once and reuse it when evaluating repeatedly.
+pub fn sparse_jacobian<F: VectorFunction>(f: &F, x: &[f64]) -> SparseMatrix {
+    SparseJacobian::new(f, x.len()).eval(f, x)
+}
@@ -0,0 +1,514 @@
+use std::f64::consts::{FRAC_2_SQRT_PI, LN_2, LN_10};
+use std::ops::{Neg, Add, Sub, Mul, Div};
+
+use crate::ops::Ops;
+use crate::real::Real;
+use crate::scalar::{impl_mixed_scalars, Scalar};
+
+/// A truncated Taylor series (jet) `x + Σ c[k-1] tᵏ` for `k = 1..=K`.
+///
+/// The coefficients are normalized: the `k`-th one is `f⁽ᵏ⁾ / k!`, so
+/// [`Taylor::derivative`] rescales by `k!`. Arithmetic is exact up to order
+/// `K`; with `K = 1` every operation matches [`Dual`](crate::Dual).
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Taylor<const K: usize, T = f64> {
+    x: T,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
+    c: [T; K],
+}
+
+impl<const K: usize, T: Real> Taylor<K, T> {
+    /// Creates a series from its value and its normalized coefficients of
+    /// orders `1..=K`.
+    pub fn new(x: T, c: [T; K]) -> Self {
+        Self { x, c }
+    }
+
+    /// Creates an independent variable, i.e. `x + t`.
+    pub fn variable(x: T) -> Self {
+        let mut c = [T::zero(); K];
+        if K > 0 {
+            c[0] = T::one();
+        }
+        Self { x, c }
+    }
+
+    /// Creates a constant.
+    pub fn constant(x: T) -> Self {
+        Self {
+            x,
+            c: [T::zero(); K],
+        }
+    }
+
+    /// Returns the primal value.
+    pub fn value(&self) -> T {
+        self.x
+    }
+
+    /// Returns the first derivative.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `K == 0`.
+    pub fn deriv(&self) -> T {
+        self.c[0]
+    }
+
+    /// Returns the normalized coefficient of order `k`, `f⁽ᵏ⁾ / k!`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > K`.
+    pub fn coeff(&self, k: usize) -> T {
+        if k == 0 {
+            self.x
+        } else {
+            self.c[k - 1]
+        }
+    }
+
+    /// Returns the `k`-th derivative, `k! · coeff(k)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > K`.
+    pub fn derivative(&self, k: usize) -> T {
+        let factorial = (1..=k).fold(1.0, |acc, i| acc * i as f64);
+        self.coeff(k) * T::from_f64(factorial)
+    }
+
+    /// Computes `sin` and `cos` together; each recurrence feeds the other.
+    pub fn sin_cos(self) -> (Self, Self) {
+        let (mut s, mut c) = (Self::constant(self.x.sin()), Self::constant(self.x.cos()));
+        for k in 1..=K {
+            let (mut sk, mut ck) = (T::zero(), T::zero());
+            for j in 1..=k {
+                let ja = T::from_f64(j as f64) * self.coeff(j);
+                sk = sk + ja * c.coeff(k - j);
+                ck = ck - ja * s.coeff(k - j);
+            }
+            let kf = T::from_f64(k as f64);
+            s.c[k - 1] = sk / kf;
+            c.c[k - 1] = ck / kf;
+        }
+        (s, c)
+    }
+
+    /// Computes `sinh` and `cosh` together; each recurrence feeds the other.
+    pub fn sinh_cosh(self) -> (Self, Self) {
+        let (mut s, mut c) = (Self::constant(self.x.sinh()), Self::constant(self.x.cosh()));
+        for k in 1..=K {
+            let (mut sk, mut ck) = (T::zero(), T::zero());
+            for j in 1..=k {
+                let ja = T::from_f64(j as f64) * self.coeff(j);
+                sk = sk + ja * c.coeff(k - j);
+                ck = ck + ja * s.coeff(k - j);
+            }
+            let kf = T::from_f64(k as f64);
+            s.c[k - 1] = sk / kf;
+            c.c[k - 1] = ck / kf;
+        }
+        (s, c)
+    }
+
+    /// Whether every coefficient above the value is exactly zero. The
+    /// recurrences that divide by the value or by a derivative skip such
+    /// constants, whose series is just `f(x)` even where `f'(x)` is
+    /// infinite.
+    fn is_constant(&self) -> bool {
+        self.c.iter().all(T::is_zero)
+    }
+
+    /// Replaces the value, keeping the higher-order coefficients. Used where
+    /// the value has a more accurate direct formula than the recurrence.
+    fn with_value(self, x: T) -> Self {
+        Self { x, c: self.c }
+    }
+
+    /// The series of `d/dt self`. Its order-`K` coefficient is unknown and
+    /// left at zero, which is harmless wherever it's used below.
+    fn d_dt(self) -> Self {
+        let mut d = Self::constant(T::zero());
+        for (m, &a) in self.c.iter().enumerate() {
+            let v = T::from_f64((m + 1) as f64) * a;
+            if m == 0 {
+                d.x = v;
+            } else {
+                d.c[m - 1] = v;
+            }
+        }
+        d
+    }
+
+    /// The series `f` with `f(0) = f0` and `df/dt = d`.
+    fn antiderivative(f0: T, d: Self) -> Self {
+        let mut f = Self::constant(f0);
+        for k in 1..=K {
+            f.c[k - 1] = d.coeff(k - 1) / T::from_f64(k as f64);
+        }
+        f
+    }
+
+    /// The series of `f(self)` given `f(x)` and the series of `f'(self)`.
+    fn compose(self, fx: T, df: Self) -> Self {
+        if self.is_constant() {
+            return Self::constant(fx);
+        }
+        Self::antiderivative(fx, df * self.d_dt())
+    }
+}
+
+impl<const K: usize, T: Real> Neg for Taylor<K, T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            x: -self.x,
+            c: self.c.map(|c| -c),
+        }
+    }
+}
+
+impl<const K: usize, T: Real> Add for Taylor<K, T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            x: self.x + rhs.x,
+            c: std::array::from_fn(|k| self.c[k] + rhs.c[k]),
+        }
+    }
+}
+
+impl<const K: usize, T: Real> Sub for Taylor<K, T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            x: self.x - rhs.x,
+            c: std::array::from_fn(|k| self.c[k] - rhs.c[k]),
+        }
+    }
+}
+
+/// Cauchy product, truncated at order `K`.
+impl<const K: usize, T: Real> Mul for Taylor<K, T> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            x: self.x * rhs.x,
+            c: std::array::from_fn(|k| cauchy(&self, &rhs, k + 1)),
+        }
+    }
+}
+
+/// Series division: solves `q · rhs = self` order by order.
+impl<const K: usize, T: Real> Div for Taylor<K, T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let mut q = Self::constant(self.x / rhs.x);
+        for k in 1..=K {
+            // `q_k b_0 = a_k - Σ_{j<k} q_j b_{k-j}`, i.e. `a_k - (q·b)_k` with
+            // the not-yet-known `q_k` still zero.
+            q.c[k - 1] = (self.c[k - 1] - cauchy(&q, &rhs, k)) / rhs.x;
+        }
+        q
+    }
+}
+
+impl<const K: usize, T: Real> Add<T> for Taylor<K, T> {
+    type Output = Self;
+    fn add(self, rhs: T) -> Self {
+        Self {
+            x: self.x + rhs,
+            c: self.c,
+        }
+    }
+}
+
+impl<const K: usize, T: Real> Sub<T> for Taylor<K, T> {
+    type Output = Self;
+    fn sub(self, rhs: T) -> Self {
+        Self {
+            x: self.x - rhs,
+            c: self.c,
+        }
+    }
+}
+
+impl<const K: usize, T: Real> Mul<T> for Taylor<K, T> {
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        Self {
+            x: self.x * rhs,
+            c: self.c.map(|c| c * rhs),
+        }
+    }
+}
+
+impl<const K: usize, T: Real> Div<T> for Taylor<K, T> {
+    type Output = Self;
+    fn div(self, rhs: T) -> Self {
+        Self {
+            x: self.x / rhs,
+            c: self.c.map(|c| c / rhs),
+        }
+    }
+}
+
+impl<const K: usize, T: Real> Scalar<T> for Taylor<K, T> {
+    fn zero() -> Self {
+        Self::constant(T::zero())
+    }
+
+    fn one() -> Self {
+        Self::constant(T::one())
+    }
+
+    fn from_f64(x: f64) -> Self {
+        Self::constant(T::from_f64(x))
+    }
+
+    fn to_f64(&self) -> f64 {
+        self.x.to_f64()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.x.is_zero() && self.c.iter().all(T::is_zero)
+    }
+}
+
+impl_mixed_scalars!(
+    [const K: usize,] Taylor [K],
+    |lhs, rhs| Taylor::constant(lhs) / rhs,
+    sigmoid: provided
+);
+
+impl<const K: usize, T: Real> Ops for Taylor<K, T> {
+    fn exp(self) -> Self {
+        let mut e = Self::constant(self.x.exp());
+        for k in 1..=K {
+            let s = (1..=k).fold(T::zero(), |acc, j| {
+                acc + T::from_f64(j as f64) * self.coeff(j) * e.coeff(k - j)
+            });
+            e.c[k - 1] = s / T::from_f64(k as f64);
+        }
+        e
+    }
+
+    fn ln(self) -> Self {
+        let mut l = Self::constant(self.x.ln());
+        if self.is_constant() {
+            return l;
+        }
+        for k in 1..=K {
+            let s = (1..k).fold(T::zero(), |acc, j| {
+                acc + T::from_f64(j as f64) * l.coeff(j) * self.coeff(k - j)
+            });
+            l.c[k - 1] = (self.c[k - 1] - s / T::from_f64(k as f64)) / self.x;
+        }
+        l
+    }
+
+    fn sin(self) -> Self {
+        self.sin_cos().0
+    }
+
+    fn cos(self) -> Self {
+        self.sin_cos().1
+    }
+
+    fn tan(self) -> Self {
+        let (sin, cos) = self.sin_cos();
+        (sin / cos).with_value(self.x.tan())
+    }
+
+    /// Repeated squaring, so `x = 0` is handled exactly for `n >= 0`.
+    fn powi(self, n: i32) -> Self {
+        let mut acc = Self::constant(T::one());
+        let mut base = self;
+        let mut m = n.unsigned_abs();
+        while m > 0 {
+            if m & 1 == 1 {
+                acc = acc * base;
+            }
+            base = base * base;
+            m >>= 1;
+        }
+        if n < 0 {
+            Self::constant(T::one()) / acc
+        } else {
+            acc
+        }
+    }
+
+    fn sqrt(self) -> Self {
+        let mut s = Self::constant(self.x.sqrt());
+        if self.is_constant() {
+            return s;
+        }
+        let two_s0 = T::from_f64(2.0) * s.x;
+        for k in 1..=K {
+            let sum = (1..k).fold(T::zero(), |acc, j| acc + s.coeff(j) * s.coeff(k - j));
+            s.c[k - 1] = (self.c[k - 1] - sum) / two_s0;
+        }
+        s
+    }
+
+    fn cbrt(self) -> Self {
+        pow_series(self, 1.0 / 3.0, self.x.cbrt())
+    }
+
+    fn powf(self, n: f64) -> Self {
+        pow_series(self, n, self.x.powf(n))
+    }
+
+    fn pow(self, y: Self) -> Self {
+        (y * self.ln()).exp().with_value(self.x.pow(y.x))
+    }
+
+    fn exp2(self) -> Self {
+        (self * T::from_f64(LN_2)).exp().with_value(self.x.exp2())
+    }
+
+    fn exp_m1(self) -> Self {
+        self.exp().with_value(self.x.exp_m1())
+    }
+
+    fn ln_1p(self) -> Self {
+        (self + T::one()).ln().with_value(self.x.ln_1p())
+    }
+
+    fn log2(self) -> Self {
+        (self.ln() / T::from_f64(LN_2)).with_value(self.x.log2())
+    }
+
+    fn log10(self) -> Self {
+        (self.ln() / T::from_f64(LN_10)).with_value(self.x.log10())
+    }
+
+    fn log(self, base: f64) -> Self {
+        (self.ln() / T::from_f64(base.ln())).with_value(self.x.log(base))
+    }
+
+    fn asin(self) -> Self {
+        let df = (-(self * self) + T::one()).powf(-0.5);
+        self.compose(self.x.asin(), df)
+    }
+
+    fn acos(self) -> Self {
+        let df = -(-(self * self) + T::one()).powf(-0.5);
+        self.compose(self.x.acos(), df)
+    }
+
+    fn atan(self) -> Self {
+        let df = Self::constant(T::one()) / (self * self + T::one());
+        self.compose(self.x.atan(), df)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        let (y, x) = (self, other);
+        if y.is_constant() && x.is_constant() {
+            return Self::constant(y.x.atan2(x.x));
+        }
+        let d = (x * y.d_dt() - y * x.d_dt()) / (x * x + y * y);
+        Self::antiderivative(y.x.atan2(x.x), d)
+    }
+
+    fn sinh(self) -> Self {
+        self.sinh_cosh().0
+    }
+
+    fn cosh(self) -> Self {
+        self.sinh_cosh().1
+    }
+
+    fn tanh(self) -> Self {
+        let (sinh, cosh) = self.sinh_cosh();
+        (sinh / cosh).with_value(self.x.tanh())
+    }
+
+    fn asinh(self) -> Self {
+        let df = (self * self + T::one()).powf(-0.5);
+        self.compose(self.x.asinh(), df)
+    }
+
+    fn acosh(self) -> Self {
+        let df = (self * self - T::one()).powf(-0.5);
+        self.compose(self.x.acosh(), df)
+    }
+
+    fn atanh(self) -> Self {
+        let df = Self::constant(T::one()) / (-(self * self) + T::one());
+        self.compose(self.x.atanh(), df)
+    }
+
+    fn erf(self) -> Self {
+        let df = (-(self * self)).exp() * T::from_f64(FRAC_2_SQRT_PI);
+        self.compose(self.x.erf(), df)
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        (self * self + other * other).sqrt().with_value(self.x.hypot(other.x))
+    }
+
+    fn abs(self) -> Self {
+        self * self.x.signum()
+    }
+
+    fn signum(self) -> Self {
+        Self::constant(self.x.signum())
+    }
+}
+
+/// Order-`k` coefficient of the product `a · b`, for `k >= 1`.
+///
+/// A term whose higher-order factor is exactly zero is skipped, as the
+/// dual types skip zero tangent entries, so an infinite value times a
+/// constant stays a constant rather than picking up NaN.
+fn cauchy<const K: usize, T: Real>(a: &Taylor<K, T>, b: &Taylor<K, T>, k: usize) -> T {
+    (0..=k).fold(T::zero(), |acc, j| {
+        let (p, q) = (a.coeff(j), b.coeff(k - j));
+        if (j > 0 && p.is_zero()) || (j < k && q.is_zero()) {
+            acc
+        } else {
+            acc + p * q
+        }
+    })
+}
+
+/// `a^r` by the recurrence `k a₀ p_k = Σ ((r + 1) j - k) a_j p_{k-j}`,
+/// starting from the given `p0 = a₀^r`.
+///
+/// The recurrence divides by `a₀`, so at `a₀ = 0` a non-negative integer
+/// `r` goes through [`Ops::powi`], which is exact there. Any other `r` has
+/// a branch point at zero; its coefficients are the leading terms
+/// `C(r, k) · 0^(r-k) · a₁ᵏ`, which are zero below order `r`, infinite above
+/// it, and NaN where `a₁ = 0` leaves them undetermined. A constant `a` is
+/// just `a₀^r`.
+fn pow_series<const K: usize, T: Real>(a: Taylor<K, T>, r: f64, p0: T) -> Taylor<K, T> {
+    if a.is_constant() {
+        return Taylor::constant(p0);
+    }
+    if a.x.to_f64() == 0.0 {
+        if r >= 0.0 && r.fract() == 0.0 && r <= i32::MAX as f64 {
+            return a.powi(r as i32);
+        }
+        let mut p = Taylor::constant(p0);
+        let mut binom = 1.0;
+        for k in 1..=K {
+            binom *= (r - (k - 1) as f64) / k as f64;
+            p.c[k - 1] = T::from_f64(binom) * a.x.powf(r - k as f64) * a.c[0].powi(k as i32);
+        }
+        return p;
+    }
+    let mut p = Taylor::constant(p0);
+    for k in 1..=K {
+        let s = (1..=k).fold(T::zero(), |acc, j| {
+            let w = T::from_f64((r + 1.0) * j as f64 - k as f64);
+            acc + w * a.coeff(j) * p.coeff(k - j)
+        });
+        p.c[k - 1] = s / (T::from_f64(k as f64) * a.x);
+    }
+    p
+}
@@ -0,0 +1,487 @@
+use std::cell::RefCell;
+use std::ops::{Neg, Add, Sub, Mul, Div};
+
+use crate::activation::Sigmoid;
+use crate::bitset::BitSet;
+use crate::ops::Ops;
+use crate::scalar::Scalar;
+
+/// Dependency sets of every value traced with [`Tracer`].
+///
+/// Running a function on [`TraceTape::inputs`] and reading back
+/// [`TraceTape::deps`] of each output gives the Jacobian sparsity pattern;
+/// [`jacobian_sparsity`](crate::jacobian_sparsity) does exactly that.
+#[derive(Debug)]
+pub struct TraceTape {
+    sets: RefCell<Vec<BitSet>>,
+}
+
+impl TraceTape {
+    /// Creates a tape whose first `n` sets are the inputs `{0}`, ..., `{n-1}`.
+    pub fn new(n: usize) -> Self {
+        Self {
+            sets: RefCell::new((0..n).map(BitSet::singleton).collect()),
+        }
+    }
+
+    /// Returns the `n` input tracers.
+    pub fn inputs(&self) -> Vec<Tracer<'_>> {
+        (0..self.sets.borrow().len())
+            .map(|id| Tracer { tape: Some(self), id })
+            .collect()
+    }
+
+    /// Returns the inputs `t` depends on, in increasing order.
+    pub fn deps(&self, t: &Tracer) -> Vec<usize> {
+        match t.tape {
+            Some(_) => self.sets.borrow()[t.id].iter().collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+/// A value-less scalar that only records which inputs it depends on, as a
+/// bitset on a [`TraceTape`].
+///
+/// Operations that keep the dependency set (every unary function) return
+/// the operand unchanged; only a binary operation between two different
+/// non-constant sets allocates a new one. Dependencies are structural:
+/// multiplying by a literal zero still depends on the other operand.
+#[derive(Debug, Copy, Clone)]
+pub struct Tracer<'a> {
+    tape: Option<&'a TraceTape>,
+    id: usize,
+}
+
+impl<'a> Tracer<'a> {
+    /// Creates a constant, which depends on nothing.
+    pub fn constant() -> Self {
+        Self { tape: None, id: 0 }
+    }
+
+    /// Unary operations keep the dependency set.
+    fn unary(self) -> Self {
+        self
+    }
+
+    /// Binary operations depend on the union of both sets.
+    fn binary(self, other: Self) -> Self {
+        match (self.tape, other.tape) {
+            (Some(tape), Some(other_tape)) if self.id != other.id => {
+                assert!(std::ptr::eq(tape, other_tape), "Tracer operands are on different tapes");
+                let mut sets = tape.sets.borrow_mut();
+                let set = sets[self.id].union(&sets[other.id]);
+                sets.push(set);
+                Self {
+                    tape: Some(tape),
+                    id: sets.len() - 1,
+                }
+            }
+            (Some(_), _) => self,
+            (None, _) => other,
+        }
+    }
+}
+
+/// Gradient and Hessian dependency sets of every value traced with
+/// [`HessianTracer`].
+#[derive(Debug)]
+pub struct HessianTape {
+    nodes: RefCell<Vec<HessianNode>>,
+}
+
+/// The inputs a value depends on, and the input pairs `(i, j)`, `i <= j`,
+/// whose mixed second derivative may be nonzero, sorted.
+#[derive(Debug, Clone)]
+struct HessianNode {
+    grad: BitSet,
+    hess: Vec<(usize, usize)>,
+}
+
+impl HessianTape {
+    /// Creates a tape whose first `n` nodes are the inputs.
+    pub fn new(n: usize) -> Self {
+        let nodes = (0..n)
+            .map(|i| HessianNode {
+                grad: BitSet::singleton(i),
+                hess: Vec::new(),
+            })
+            .collect();
+        Self {
+            nodes: RefCell::new(nodes),
+        }
+    }
+
+    /// Returns the `n` input tracers.
+    pub fn inputs(&self) -> Vec<HessianTracer<'_>> {
+        (0..self.nodes.borrow().len())
+            .map(|id| HessianTracer { tape: Some(self), id })
+            .collect()
+    }
+
+    /// Returns the inputs `t` depends on, in increasing order.
+    pub fn deps(&self, t: &HessianTracer) -> Vec<usize> {
+        match t.tape {
+            Some(_) => self.nodes.borrow()[t.id].grad.iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Returns the pairs `(i, j)`, `i <= j`, for which `∂²t/∂xᵢ∂xⱼ` may be
+    /// nonzero, sorted.
+    pub fn hessian(&self, t: &HessianTracer) -> Vec<(usize, usize)> {
+        match t.tape {
+            Some(_) => self.nodes.borrow()[t.id].hess.clone(),
+            None => Vec::new(),
+        }
+    }
+
+    fn push(&self, node: HessianNode) -> usize {
+        let mut nodes = self.nodes.borrow_mut();
+        nodes.push(node);
+        nodes.len() - 1
+    }
+}
+
+/// Adds every pair from `a × b` to `hess`, ordered as `(min, max)`.
+fn outer(hess: &mut Vec<(usize, usize)>, a: &BitSet, b: &BitSet) {
+    for i in a.iter() {
+        for j in b.iter() {
+            hess.push((i.min(j), i.max(j)));
+        }
+    }
+}
+
+/// How a binary operation's second derivatives couple its operands.
+#[derive(Copy, Clone)]
+enum Coupling {
+    /// `a ± b`: no second derivatives.
+    Linear,
+    /// `a · b`: only the mixed term.
+    Product,
+    /// `a / b`: the mixed term and `b × b`.
+    Quotient,
+    /// Any other function of both operands.
+    Full,
+}
+
+/// A value-less scalar that records the inputs a value depends on and the
+/// input pairs its Hessian may couple, on a [`HessianTape`].
+///
+/// Linear operations (negation, sums, scaling by constants) and `abs`,
+/// which is piecewise linear, add no Hessian entries. Every other unary
+/// function couples all pairs of its operand's inputs, and products and
+/// quotients couple their operands as described on each operator.
+#[derive(Debug, Copy, Clone)]
+pub struct HessianTracer<'a> {
+    tape: Option<&'a HessianTape>,
+    id: usize,
+}
+
+impl<'a> HessianTracer<'a> {
+    /// Creates a constant, which depends on nothing.
+    pub fn constant() -> Self {
+        Self { tape: None, id: 0 }
+    }
+
+    /// A nonlinear unary function: `H ∪ (G × G)`.
+    fn unary(self) -> Self {
+        let Some(tape) = self.tape else {
+            return self;
+        };
+        let mut node = tape.nodes.borrow()[self.id].clone();
+        outer(&mut node.hess, &node.grad, &node.grad);
+        node.hess.sort_unstable();
+        node.hess.dedup();
+        Self {
+            tape: Some(tape),
+            id: tape.push(node),
+        }
+    }
+
+    fn binary(self, other: Self) -> Self {
+        self.couple(other, Coupling::Full)
+    }
+
+    fn couple(self, other: Self, coupling: Coupling) -> Self {
+        let tape = match (self.tape, other.tape) {
+            (Some(tape), Some(other_tape)) => {
+                assert!(std::ptr::eq(tape, other_tape), "HessianTracer operands are on different tapes");
+                tape
+            }
+            // A constant operand turns any coupling into a unary function
+            // of the other one, linear for sums and products.
+            (Some(_), None) => {
+                return match coupling {
+                    Coupling::Linear | Coupling::Product | Coupling::Quotient => self,
+                    Coupling::Full => self.unary(),
+                };
+            }
+            (None, Some(_)) => {
+                return match coupling {
+                    Coupling::Linear | Coupling::Product => other,
+                    Coupling::Quotient | Coupling::Full => other.unary(),
+                };
+            }
+            (None, None) => return self,
+        };
+        let node = {
+            let nodes = tape.nodes.borrow();
+            let (a, b) = (&nodes[self.id], &nodes[other.id]);
+            let grad = a.grad.union(&b.grad);
+            let mut hess: Vec<_> = a.hess.iter().chain(&b.hess).copied().collect();
+            match coupling {
+                Coupling::Linear => {}
+                Coupling::Product => outer(&mut hess, &a.grad, &b.grad),
+                Coupling::Quotient => {
+                    outer(&mut hess, &a.grad, &b.grad);
+                    outer(&mut hess, &b.grad, &b.grad);
+                }
+                Coupling::Full => outer(&mut hess, &grad, &grad),
+            }
+            hess.sort_unstable();
+            hess.dedup();
+            HessianNode { grad, hess }
+        };
+        Self {
+            tape: Some(tape),
+            id: tape.push(node),
+        }
+    }
+}
+
+impl<'a> Neg for Tracer<'a> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl<'a> Add for Tracer<'a> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        self.binary(rhs)
+    }
+}
+
+impl<'a> Sub for Tracer<'a> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self.binary(rhs)
+    }
+}
+
+impl<'a> Mul for Tracer<'a> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.binary(rhs)
+    }
+}
+
+impl<'a> Div for Tracer<'a> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        self.binary(rhs)
+    }
+}
+
+impl<'a> Neg for HessianTracer<'a> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl<'a> Add for HessianTracer<'a> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        self.couple(rhs, Coupling::Linear)
+    }
+}
+
+impl<'a> Sub for HessianTracer<'a> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self.couple(rhs, Coupling::Linear)
+    }
+}
+
+impl<'a> Mul for HessianTracer<'a> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        self.couple(rhs, Coupling::Product)
+    }
+}
+
+impl<'a> Div for HessianTracer<'a> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        self.couple(rhs, Coupling::Quotient)
+    }
+}
+
+// Plain scalars are constants: adding, subtracting or scaling by one is
+// linear, dividing a scalar by a tracer is not.
+macro_rules! impl_scalar {
+    ($($ty:ident),*) => {$(
+        impl<'a> Add<f64> for $ty<'a> {
+            type Output = Self;
+            fn add(self, _: f64) -> Self {
+                self
+            }
+        }
+
+        impl<'a> Sub<f64> for $ty<'a> {
+            type Output = Self;
+            fn sub(self, _: f64) -> Self {
+                self
+            }
+        }
+
+        impl<'a> Mul<f64> for $ty<'a> {
+            type Output = Self;
+            fn mul(self, _: f64) -> Self {
+                self
+            }
+        }
+
+        impl<'a> Div<f64> for $ty<'a> {
+            type Output = Self;
+            fn div(self, _: f64) -> Self {
+                self
+            }
+        }
+
+        impl<'a> Add<$ty<'a>> for f64 {
+            type Output = $ty<'a>;
+            fn add(self, rhs: $ty<'a>) -> $ty<'a> {
+                rhs
+            }
+        }
+
+        impl<'a> Sub<$ty<'a>> for f64 {
+            type Output = $ty<'a>;
+            fn sub(self, rhs: $ty<'a>) -> $ty<'a> {
+                rhs
+            }
+        }
+
+        impl<'a> Mul<$ty<'a>> for f64 {
+            type Output = $ty<'a>;
+            fn mul(self, rhs: $ty<'a>) -> $ty<'a> {
+                rhs
+            }
+        }
+
+        impl<'a> Div<$ty<'a>> for f64 {
+            type Output = $ty<'a>;
+            fn div(self, rhs: $ty<'a>) -> $ty<'a> {
+                rhs.unary()
+            }
+        }
+
+        impl<'a> Sigmoid<f64> for $ty<'a> {}
+
+        impl<'a> Scalar for $ty<'a> {
+            fn zero() -> Self {
+                Self::constant()
+            }
+
+            fn one() -> Self {
+                Self::constant()
+            }
+
+            fn from_f64(_: f64) -> Self {
+                Self::constant()
+            }
+
+            fn to_f64(&self) -> f64 {
+                f64::NAN
+            }
+
+            fn is_zero(&self) -> bool {
+                false
+            }
+        }
+
+        impl<'a> Scalar<$ty<'a>> for $ty<'a> {
+            fn zero() -> Self {
+                Self::constant()
+            }
+
+            fn one() -> Self {
+                Self::constant()
+            }
+
+            fn from_f64(_: f64) -> Self {
+                Self::constant()
+            }
+
+            fn to_f64(&self) -> f64 {
+                f64::NAN
+            }
+
+            fn is_zero(&self) -> bool {
+                false
+            }
+        }
+    )*};
+}
+
+impl_scalar!(Tracer, HessianTracer);
+
+// Every elementary function either keeps its operand's inputs (`unary`),
+// merges two operands' (`binary`), or is constant.
+macro_rules! impl_ops {
+    ($($ty:ident),*) => {$(
+        impl<'a> Ops for $ty<'a> {
+            impl_ops!(@unary exp, ln, sin, cos, tan, sqrt, cbrt, exp2, exp_m1, ln_1p, log2,
+                log10, asin, acos, atan, sinh, cosh, tanh, asinh, acosh, atanh, erf);
+            impl_ops!(@binary pow, atan2, hypot);
+
+            fn powi(self, n: i32) -> Self {
+                match n {
+                    0 => Self::constant(),
+                    1 => self,
+                    _ => self.unary(),
+                }
+            }
+
+            fn powf(self, n: f64) -> Self {
+                if n == 0.0 {
+                    Self::constant()
+                } else if n == 1.0 {
+                    self
+                } else {
+                    self.unary()
+                }
+            }
+
+            fn log(self, _: f64) -> Self {
+                self.unary()
+            }
+
+            /// Piecewise linear: keeps the inputs, adds no Hessian entries.
+            fn abs(self) -> Self {
+                self
+            }
+
+            /// Piecewise constant: depends on nothing in the derivative sense.
+            fn signum(self) -> Self {
+                Self::constant()
+            }
+        }
+    )*};
+    (@unary $($method:ident),*) => {$(
+        fn $method(self) -> Self {
+            self.unary()
+        }
+    )*};
+    (@binary $($method:ident),*) => {$(
+        fn $method(self, other: Self) -> Self {
+            self.binary(other)
+        }
+    )*};
+}
+
+impl_ops!(Tracer, HessianTracer);
@@ -0,0 +1,158 @@
+use std::f64::consts::LN_2;
+
+use dual::{Dual, DualN, DualVec, HyperDual, Ops, Sigmoid, SparseDual, Tape, Taylor, TraceTape};
+
+mod common;
+
+use common::FiniteDiff;
+
+const FD: FiniteDiff = FiniteDiff { h: 1e-4, tol: 1e-9 };
+
+/// Compares the `Dual` derivative with a fourth-order central difference
+/// of the `f64` evaluation at each point.
+macro_rules! check {
+    ($name:expr, $points:expr, |$v:ident| $body:expr) => {{
+        let f = |$v: f64| -> f64 { $body };
+        let g = |$v: Dual| -> Dual { $body };
+        for x in $points {
+            let y = g(Dual::variable(x));
+            let at = |what| format!("{} {what} at x = {x}", $name);
+            FD.assert_close(y.value(), f(x), at("value"));
+            FD.assert_close(y.deriv(), FD.central4(f, x), at("deriv"));
+        }
+    }};
+}
+
+#[test]
+fn values() {
+    assert_eq!(2.5.relu(), 2.5);
+    assert_eq!((-2.5).relu(), 0.0);
+    assert_eq!((-2.0).leaky_relu(0.1), -0.2);
+    FD.assert_close((-1.0).elu(1.0), -0.6321205588285577, "elu");
+    FD.assert_close((-1.0).selu(), -1.1113307378125625, "selu");
+    FD.assert_close(1.0.gelu(), 0.8413447460685429, "gelu");
+    FD.assert_close((-0.5).gelu(), -0.15426876936299344, "gelu");
+    FD.assert_close(1.0.gelu_tanh(), 0.8411919906082768, "gelu_tanh");
+    FD.assert_close(1.0.silu(), 0.7310585786300049, "silu");
+    FD.assert_close(0.5.swish(2.0), 0.7310585786300049 / 2.0, "swish");
+    FD.assert_close(1.0.mish(), 0.8650983882673103, "mish");
+    FD.assert_close(0.0.softplus(), LN_2, "softplus");
+    assert_eq!((-3.0).softsign(), -0.75);
+    assert_eq!(1.5.hard_sigmoid(), 0.75);
+    assert_eq!(1.5.hard_swish(), 1.125);
+    FD.assert_close(0.0.log_sigmoid(), -LN_2, "log_sigmoid");
+}
+
+#[test]
+fn smooth_derivatives() {
+    let points = [-4.0, -1.3, -0.2, 0.0, 0.4, 1.7, 5.0];
+    check!("selu", [-2.0, -0.5, 0.5, 2.0], |x| x.selu());
+    check!("gelu", points, |x| x.gelu());
+    check!("gelu_tanh", points, |x| x.gelu_tanh());
+    check!("swish", points, |x| x.swish(1.5));
+    check!("silu", points, |x| x.silu());
+    check!("mish", points, |x| x.mish());
+    check!("softplus", points, |x| x.softplus());
+    check!("softsign", [-4.0, -0.2, 0.4, 5.0], |x| x.softsign());
+    check!("log_sigmoid", points, |x| x.log_sigmoid());
+}
+
+#[test]
+fn piecewise_derivatives() {
+    let points = [-5.0, -2.9, -1.0, 0.3, 2.9, 5.0];
+    check!("relu", points, |x| x.relu());
+    check!("leaky_relu", points, |x| x.leaky_relu(0.01));
+    check!("elu", points, |x| x.elu(0.7));
+    check!("hard_sigmoid", points, |x| x.hard_sigmoid());
+    check!("hard_swish", points, |x| x.hard_swish());
+}
+
+#[test]
+fn subgradients_at_kinks() {
+    let d = |f: fn(Dual) -> Dual, x: f64| f(Dual::variable(x)).deriv();
+    assert_eq!(d(|x| x.relu(), 0.0), 0.0);
+    assert_eq!(d(|x| x.leaky_relu(0.1), 0.0), 0.1);
+    assert_eq!(d(|x| x.elu(0.5), 0.0), 0.5);
+    assert_eq!(d(|x| x.hard_sigmoid(), -3.0), 0.0);
+    assert_eq!(d(|x| x.hard_sigmoid(), 3.0), 0.0);
+    assert_eq!(d(|x| x.hard_swish(), -3.0), 0.0);
+    assert_eq!(d(|x| x.hard_swish(), 3.0), 1.0);
+    assert!(f64::NAN.relu().is_nan());
+}
+
+#[test]
+fn softplus_is_stable() {
+    let y = Dual::variable(800.0).softplus();
+    assert_eq!((y.value(), y.deriv()), (800.0, 1.0));
+    let y = Dual::variable(-800.0).softplus();
+    assert_eq!((y.value(), y.deriv()), (0.0, 0.0));
+    assert_eq!((-800.0).log_sigmoid(), -800.0);
+}
+
+#[test]
+fn other_scalar_types() {
+    let tape = Tape::new();
+    let x = tape.var(0.8);
+    let y = x.mish() + x.relu();
+    let expected = Dual::variable(0.8).mish().deriv() + 1.0;
+    FD.assert_close(y.backward().wrt(&x), expected, "Var");
+
+    // Tracers have no value, so piecewise functions keep the dependency.
+    let tape = TraceTape::new(2);
+    let x = tape.inputs();
+    assert_eq!(tape.deps(&x[1].relu()), vec![1]);
+    assert_eq!(tape.deps(&(x[0].hard_swish() + x[1].exp())), vec![0, 1]);
+}
+
+#[test]
+fn sigmoid_is_stable_at_large_magnitude() {
+    for (x, s) in [(1e3, 1.0), (-1e3, 0.0)] {
+        assert_eq!(x.sigmoid(), s);
+        assert_eq!((x as f32).sigmoid(), s as f32);
+        let y = Dual::variable(x).sigmoid();
+        assert_eq!((y.value(), y.deriv()), (s, 0.0));
+        assert_eq!(DualN::<2>::variable(x, 0).sigmoid().deriv(), [0.0, 0.0]);
+        assert_eq!(DualVec::variable(x, 0, 1).sigmoid().deriv(), &[0.0]);
+        assert_eq!(SparseDual::variable(x, 0).sigmoid().partial(0), 0.0);
+        let y = HyperDual::variable(x).sigmoid();
+        assert_eq!((y.value(), y.eps1(), y.eps12()), (s, 0.0, 0.0));
+        assert_eq!(Taylor::<3>::variable(x).sigmoid().derivative(3), 0.0);
+        assert_eq!(Dual::variable(Dual::variable(x)).sigmoid().deriv().deriv(), 0.0);
+        let tape = Tape::new();
+        let v = tape.var(x);
+        assert_eq!(v.sigmoid().backward().wrt(&v), 0.0);
+    }
+    let y = Dual::variable(-30.0).sigmoid();
+    FD.assert_close(y.deriv() / y.value(), 1.0 - y.value(), "relative sigmoid slope");
+}
+
+#[test]
+fn sigmoid_derivatives() {
+    let points = [-20.0, -3.0, -0.5, 0.0, 0.5, 3.0, 20.0];
+    check!("sigmoid", points, |x| x.sigmoid());
+    for x in points {
+        let s = x.sigmoid();
+        let y = HyperDual::variable(x).sigmoid();
+        FD.assert_close(y.eps12(), s * (1.0 - s) * (1.0 - 2.0 * s), "sigmoid''");
+        let t = Taylor::<2>::variable(x).sigmoid();
+        FD.assert_close(t.derivative(2), y.eps12(), "Taylor sigmoid''");
+    }
+}
+
+#[test]
+fn log_sigmoid_and_logit() {
+    let y = Dual::variable(1e3).log_sigmoid();
+    assert_eq!((y.value(), y.deriv()), (0.0, 0.0));
+    let y = Dual::variable(-1e3).log_sigmoid();
+    assert_eq!((y.value(), y.deriv()), (-1e3, 1.0));
+    check!("log_sigmoid", [-30.0, -2.0, 0.0, 2.0, 30.0], |x| x.log_sigmoid());
+
+    check!("logit", [0.05, 0.3, 0.5, 0.8, 0.95], |p| p.logit());
+    assert_eq!(0.5.logit(), 0.0);
+    for x in [-700.0, -30.0, -1.0, 0.0, 1.0, 10.0] {
+        let p = Dual::variable(x).sigmoid();
+        let back = p.logit();
+        FD.assert_close(back.value(), x, "logit(sigmoid(x))");
+        FD.assert_close(back.deriv(), 1.0, "d logit(sigmoid(x))");
+    }
+}
@@ -0,0 +1,137 @@
+use std::cell::Cell;
+
+use dual::{
+    derivative, directional_derivative, gradient, hessian, jacobian, Dual, Function, HyperDual, Ops,
+    Real, VectorFunction,
+};
+
+/// `Σ sin(xᵢ) xᵢ₊₁ + exp(x₀ / n)`.
+struct Chain;
+
+impl Function for Chain {
+    fn eval<T: Real>(&self, x: &[T]) -> T {
+        let n = T::from_f64(x.len() as f64);
+        x.windows(2).fold((x[0] / n).exp(), |acc, w| acc + w[0].sin() * w[1])
+    }
+}
+
+/// `ℝ² → ℝ³`: `(x y, sin x, x + y²)`.
+struct Tall;
+
+impl VectorFunction for Tall {
+    fn eval<T: Real>(&self, x: &[T]) -> Vec<T> {
+        vec![x[0] * x[1], x[0].sin(), x[0] + x[1] * x[1]]
+    }
+}
+
+/// `ℝ³ → ℝ²`: `(x y z, x - z²)`.
+struct Wide;
+
+impl VectorFunction for Wide {
+    fn eval<T: Real>(&self, x: &[T]) -> Vec<T> {
+        vec![x[0] * x[1] * x[2], x[0] - x[2] * x[2]]
+    }
+}
+
+/// `x₀ + √x₁ + Σ xᵢ` over the remaining inputs, singular in `x₁` at zero.
+struct SqrtSum;
+
+impl Function for SqrtSum {
+    fn eval<T: Real>(&self, x: &[T]) -> T {
+        x[2..].iter().fold(x[0] + x[1].sqrt(), |acc, &xi| acc + xi)
+    }
+}
+
+/// A vector function, counting its evaluations.
+struct Counted<F>(F, Cell<usize>);
+
+impl<F: VectorFunction> VectorFunction for Counted<F> {
+    fn eval<T: Real>(&self, x: &[T]) -> Vec<T> {
+        self.1.set(self.1.get() + 1);
+        self.0.eval(x)
+    }
+}
+
+fn chain_gradient(x: &[f64]) -> Vec<f64> {
+    let n = x.len();
+    (0..n)
+        .map(|i| {
+            let mut g = if i == 0 { (x[0] / n as f64).exp() / n as f64 } else { 0.0 };
+            if i + 1 < n {
+                g += x[i].cos() * x[i + 1];
+            }
+            if i > 0 {
+                g += x[i - 1].sin();
+            }
+            g
+        })
+        .collect()
+}
+
+#[test]
+fn derivative_and_directional_derivative() {
+    let (y, dy) = derivative(|x| x.sin() * x, 0.5);
+    assert_eq!(y, 0.5f64.sin() * 0.5);
+    assert_eq!(dy, 0.5f64.cos() * 0.5 + 0.5f64.sin());
+
+    let (y, dv) = directional_derivative(|x: &[Dual]| x[0] * x[1], &[2.0, 3.0], &[1.0, -1.0]);
+    assert_eq!((y, dv), (6.0, 3.0 - 2.0));
+}
+
+#[test]
+fn gradient_in_both_modes() {
+    for n in [1, 3, 4, 5, 12] {
+        let x: Vec<f64> = (0..n).map(|i| 0.3 * i as f64 - 0.7).collect();
+        let g = gradient(&Chain, &x);
+        for (a, b) in g.iter().zip(chain_gradient(&x)) {
+            assert!((a - b).abs() < 1e-14, "n = {n}");
+        }
+    }
+}
+
+#[test]
+fn gradient_does_not_depend_on_the_mode() {
+    // Two inputs take the forward branch, five the reverse one.
+    assert_eq!(gradient(&SqrtSum, &[1.0, 0.0]), [1.0, f64::INFINITY]);
+    assert_eq!(gradient(&SqrtSum, &[1.0, 0.0, 2.0, 3.0, 4.0]), [1.0, f64::INFINITY, 1.0, 1.0, 1.0]);
+}
+
+#[test]
+fn jacobian_in_both_modes() {
+    let (x, y) = (0.4, -1.5);
+    let tall = jacobian(&Tall, &[x, y]);
+    assert_eq!((tall.rows(), tall.cols()), (3, 2));
+    assert_eq!(tall.as_slice(), &[y, x, x.cos(), 0.0, 1.0, 2.0 * y]);
+
+    let z = 2.0;
+    let wide = jacobian(&Wide, &[x, y, z]);
+    assert_eq!((wide.rows(), wide.cols()), (2, 3));
+    assert_eq!(wide.row(0), &[y * z, x * z, x * y]);
+    assert_eq!(wide.row(1), &[1.0, 0.0, -2.0 * z]);
+}
+
+#[test]
+fn jacobian_runs_only_the_chosen_mode() {
+    // One plain evaluation, then a forward pass per input...
+    let tall = Counted(Tall, Cell::new(0));
+    jacobian(&tall, &[0.4, -1.5]);
+    assert_eq!(tall.1.get(), 1 + 2);
+
+    // ...or a single taped evaluation.
+    let wide = Counted(Wide, Cell::new(0));
+    jacobian(&wide, &[0.4, -1.5, 2.0]);
+    assert_eq!(wide.1.get(), 1 + 1);
+}
+
+#[test]
+fn hessian_matches_hyper_dual() {
+    let x = [0.2, -0.9, 1.3, 0.5, -0.1];
+    let h = hessian(&Chain, &x);
+    let expected = HyperDual::hessian(|v| Chain.eval(v), &x);
+    for i in 0..x.len() {
+        for j in 0..x.len() {
+            assert!((h[(i, j)] - expected[i][j]).abs() < 1e-14, "H[{i}][{j}]");
+            assert_eq!(h[(i, j)], h[(j, i)]);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use dual::{Batch, Dual, Ops, Sigmoid};
+
+fn f(x: Dual) -> Dual {
+    (x * 0.37).sin().sigmoid() * x.exp() / (x * x + 1.0)
+}
+
+fn samples() -> Vec<f64> {
+    (0..10_000).map(|i| ((i * 7919) % 10_007) as f64 / 10_007.0 * 4.0 - 2.0).collect()
+}
+
+#[test]
+fn eval_matches_sequential_in_order() {
+    let xs = samples();
+    let batch = Batch::new().threads(4).chunk_size(333).eval(f, &xs);
+    assert_eq!(batch.len(), xs.len());
+    for (b, &x) in batch.iter().zip(&xs) {
+        let d = f(Dual::variable(x));
+        assert_eq!((b.value(), b.deriv()), (d.value(), d.deriv()));
+    }
+}
+
+#[test]
+fn reductions_are_bit_reproducible_across_thread_counts() {
+    let xs = samples();
+    let reference = Batch::new().threads(1).chunk_size(100).mean(f, &xs);
+    for threads in [2, 3, 7, 16, 200] {
+        let mean = Batch::new().threads(threads).chunk_size(100).mean(f, &xs);
+        assert_eq!(mean.value().to_bits(), reference.value().to_bits());
+        assert_eq!(mean.deriv().to_bits(), reference.deriv().to_bits());
+    }
+
+    let sequential: f64 = xs.iter().map(|&x| f(Dual::variable(x)).deriv()).sum();
+    let sum = Batch::new().threads(5).chunk_size(100).sum(f, &xs);
+    assert!((sum.deriv() - sequential).abs() < 1e-9 * sequential.abs());
+}
+
+#[test]
+fn empty_input() {
+    assert!(Batch::new().eval(f, &[]).is_empty());
+    assert_eq!(Batch::new().sum(f, &[]).value(), 0.0);
+}
+
+#[test]
+#[should_panic(expected = "f is undefined at sample 5000")]
+fn worker_panics_keep_their_message() {
+    let xs = samples();
+    let bad = xs[5_000];
+    Batch::new().threads(4).chunk_size(100).eval(
+        |x| {
+            assert!(x.value() != bad, "f is undefined at sample 5000");
+            f(x)
+        },
+        &xs,
+    );
+}
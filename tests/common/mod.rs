@@ -0,0 +1,47 @@
+//! Finite-difference helpers shared by the derivative tests.
+
+// Each test crate uses only some of these.
+#![allow(dead_code)]
+
+use std::fmt::Display;
+
+/// The step and relative tolerance of a finite-difference check. Each test
+/// file picks its own: higher derivatives and lower-order stencils need a
+/// looser tolerance.
+#[derive(Debug, Copy, Clone)]
+pub struct FiniteDiff {
+    pub h: f64,
+    pub tol: f64,
+}
+
+impl FiniteDiff {
+    /// Asserts that `actual` is within `tol` of `expected`, relative to
+    /// `max(|expected|, 1)`.
+    pub fn assert_close(&self, actual: f64, expected: f64, what: impl Display) {
+        let err = (actual - expected).abs() / expected.abs().max(1.0);
+        assert!(
+            err < self.tol,
+            "{what}: got {actual}, expected {expected} (rel. err {err:e})"
+        );
+    }
+
+    /// Fourth-order central difference of `f` at `x`.
+    pub fn central4(&self, f: impl Fn(f64) -> f64, x: f64) -> f64 {
+        let h = self.h;
+        (8.0 * (f(x + h) - f(x - h)) - (f(x + 2.0 * h) - f(x - 2.0 * h))) / (12.0 * h)
+    }
+
+    /// Sixth-order central difference of `f` at `x`.
+    pub fn central6(&self, f: impl Fn(f64) -> f64, x: f64) -> f64 {
+        let h = self.h;
+        let d1 = f(x + h) - f(x - h);
+        let d2 = f(x + 2.0 * h) - f(x - 2.0 * h);
+        let d3 = f(x + 3.0 * h) - f(x - 3.0 * h);
+        (45.0 * d1 - 9.0 * d2 + d3) / (60.0 * h)
+    }
+}
+
+/// `steps + 1` evenly spaced points from `lo` to `hi`.
+pub fn grid(lo: f64, hi: f64, steps: usize) -> impl Iterator<Item = f64> {
+    (0..=steps).map(move |i| lo + (hi - lo) * i as f64 / steps as f64)
+}
@@ -0,0 +1,89 @@
+use std::cmp::Ordering;
+
+use dual::{Dual, Ops};
+
+#[test]
+fn compares_values_only() {
+    let a = Dual::new(2.0, 1.0);
+    let b = Dual::new(2.0, -5.0);
+    let c = Dual::variable(3.0);
+    assert_eq!(a, b);
+    assert!(!a.eq_full(&b));
+    assert!(a.eq_full(&Dual::new(2.0, 1.0)));
+    assert!(a < c && c > b && a <= b && a >= b);
+    assert_eq!(a.partial_cmp(&b), Some(Ordering::Equal));
+    assert_eq!(Dual::variable(f64::NAN).partial_cmp(&a), None);
+
+    assert!(a == 2.0 && a < 2.5 && a > 1.0);
+    assert!(2.0 == a && 2.5 > a && 1.0f64 < a);
+    let d = Dual::variable(2.0f32);
+    assert!(d == 2.0 && 1.0f32 < d);
+
+    let mut xs = [Dual::variable(3.0), Dual::constant(-1.0), Dual::new(0.5, 2.0)];
+    xs.sort_by(|p, q| p.partial_cmp(q).unwrap());
+    let values: Vec<f64> = xs.iter().map(|x| x.value()).collect();
+    assert_eq!(values, [-1.0, 0.5, 3.0]);
+    assert_eq!(xs[1].deriv(), 2.0);
+}
+
+#[test]
+fn eq_full_looks_through_nesting() {
+    let a = Dual::new(Dual::new(1.0, 2.0), Dual::new(3.0, 4.0));
+    let b = Dual::new(Dual::new(1.0, 2.0), Dual::new(3.0, -4.0));
+    assert!(a == b);
+    assert!(!a.eq_full(&b));
+    assert!(a.eq_full(&Dual::new(Dual::new(1.0, 2.0), Dual::new(3.0, 4.0))));
+}
+
+#[test]
+fn min_max_select_a_branch() {
+    let x = Dual::new(1.0, 10.0);
+    let y = Dual::new(2.0, 20.0);
+    assert!(x.min(y).eq_full(&x));
+    assert!(y.min(x).eq_full(&x));
+    assert!(x.max(y).eq_full(&y));
+    assert!(y.max(x).eq_full(&y));
+
+    // Ties select `self`.
+    let z = Dual::new(1.0, -3.0);
+    assert!(x.min(z).eq_full(&x));
+    assert!(z.min(x).eq_full(&z));
+    assert!(x.max(z).eq_full(&x));
+    assert!(z.max(x).eq_full(&z));
+
+    // NaN loses, like f64::min and f64::max.
+    let nan = Dual::new(f64::NAN, 1.0);
+    assert!(nan.min(x).eq_full(&x));
+    assert!(x.max(nan).eq_full(&x));
+}
+
+#[test]
+fn clamp_and_abs() {
+    let clamp = |x: f64| Dual::variable(x).clamp(-1.0, 1.0);
+    assert!(clamp(0.5).eq_full(&Dual::new(0.5, 1.0)));
+    assert!(clamp(1.5).eq_full(&Dual::new(1.0, 0.0)));
+    assert!(clamp(-3.0).eq_full(&Dual::new(-1.0, 0.0)));
+    // At a bound the value passes through with its tangent.
+    assert!(clamp(1.0).eq_full(&Dual::new(1.0, 1.0)));
+    assert!(clamp(-1.0).eq_full(&Dual::new(-1.0, 1.0)));
+    assert!(clamp(f64::NAN).value().is_nan());
+
+    assert!(Dual::variable(-2.0).abs().eq_full(&Dual::new(2.0, -1.0)));
+    assert!(Dual::variable(0.0).abs().eq_full(&Dual::new(0.0, 1.0)));
+    assert!(Dual::variable(-0.0).abs().eq_full(&Dual::new(0.0, -1.0)));
+}
+
+#[test]
+#[should_panic(expected = "lo <= hi")]
+fn clamp_rejects_inverted_bounds() {
+    Dual::variable(0.0).clamp(1.0, -1.0);
+}
+
+#[test]
+fn nested_duals_compare_by_innermost_value() {
+    let x = Dual::variable(Dual::variable(2.0f64));
+    let y = Dual::constant(Dual::constant(3.0));
+    assert!(x < y);
+    assert!(x.max(y).eq_full(&y));
+    assert_eq!(x.min(y).deriv().value(), 1.0);
+}
@@ -0,0 +1,149 @@
+//! Checks every derivative rule against a sixth-order central finite
+//! difference over a grid of inputs.
+
+use dual::{Dual, Ops, Sigmoid};
+
+mod common;
+
+use common::{grid, FiniteDiff};
+
+const FD: FiniteDiff = FiniteDiff { h: 1e-3, tol: 1e-7 };
+const STEPS: usize = 24;
+
+/// Evaluates `$f` once on `Dual` and once on `f64` at every grid point and
+/// compares value and derivative.
+macro_rules! check {
+    ($name:expr, [$lo:expr, $hi:expr], |$x:ident| $body:expr) => {{
+        let f = |$x: f64| -> f64 { $body };
+        let g = |$x: Dual| -> Dual { $body };
+        for x in grid($lo, $hi, STEPS) {
+            let y = g(Dual::variable(x));
+            let at = |what| format!("{} {what} at x = {x}", $name);
+            FD.assert_close(y.value(), f(x), at("value"));
+            FD.assert_close(y.deriv(), FD.central6(f, x), at("deriv"));
+        }
+    }};
+}
+
+/// Like `check!`, but for binary functions: each argument is differentiated
+/// in turn while the other is held constant.
+macro_rules! check2 {
+    ($name:expr, [$lo:expr, $hi:expr], [$lo2:expr, $hi2:expr], |$x:ident, $y:ident| $body:expr) => {{
+        let f = |$x: f64, $y: f64| -> f64 { $body };
+        let g = |$x: Dual, $y: Dual| -> Dual { $body };
+        for x in grid($lo, $hi, STEPS) {
+            for y in grid($lo2, $hi2, STEPS).step_by(4) {
+                let dx = g(Dual::variable(x), Dual::constant(y));
+                let dy = g(Dual::constant(x), Dual::variable(y));
+                let at = |what| format!("{} {what} at ({x}, {y})", $name);
+                FD.assert_close(dx.value(), f(x, y), at("value"));
+                FD.assert_close(dx.deriv(), FD.central6(|x| f(x, y), x), at("d/dx"));
+                FD.assert_close(dy.deriv(), FD.central6(|y| f(x, y), y), at("d/dy"));
+            }
+        }
+    }};
+}
+
+#[test]
+fn exponential_and_logarithmic() {
+    check!("exp", [-3.0, 3.0], |x| x.exp());
+    check!("exp2", [-3.0, 3.0], |x| x.exp2());
+    check!("exp_m1", [-3.0, 3.0], |x| x.exp_m1());
+    check!("ln", [0.1, 5.0], |x| x.ln());
+    check!("ln_1p", [-0.9, 5.0], |x| x.ln_1p());
+    check!("log2", [0.1, 5.0], |x| x.log2());
+    check!("log10", [0.1, 5.0], |x| x.log10());
+    check!("log", [0.1, 5.0], |x| x.log(3.0));
+}
+
+#[test]
+fn powers_and_roots() {
+    check!("powi", [-2.0, 2.0], |x| x.powi(3));
+    check!("powi negative", [0.2, 2.0], |x| x.powi(-2));
+    check!("powf", [0.1, 3.0], |x| x.powf(2.5));
+    check!("sqrt", [0.1, 5.0], |x| x.sqrt());
+    check!("cbrt", [0.1, 5.0], |x| x.cbrt());
+    check!("cbrt negative", [-5.0, -0.1], |x| x.cbrt());
+    check2!("pow", [0.2, 3.0], [-2.0, 2.0], |x, y| x.pow(y));
+    check2!("hypot", [-3.0, 3.0], [0.5, 2.0], |x, y| x.hypot(y));
+}
+
+#[test]
+fn powers_at_zero_and_negative_bases() {
+    let d = |y: Dual| (y.value(), y.deriv());
+    assert_eq!(d(Dual::variable(0.0).powi(0)), (1.0, 0.0));
+    assert_eq!(d(Dual::variable(0.0).powf(0.0)), (1.0, 0.0));
+    assert_eq!(d(Dual::variable(0.0).pow(Dual::constant(0.0))), (1.0, 0.0));
+    assert_eq!(d(Dual::variable(0.0).pow(Dual::constant(2.0))), (0.0, 0.0));
+    assert_eq!(d(Dual::variable(-2.0).pow(Dual::constant(2.0))), (4.0, -4.0));
+    assert_eq!(d(Dual::variable(-2.0).pow(Dual::constant(3.0))), (-8.0, 12.0));
+    // The exponent's own derivative still needs `ln x`.
+    assert!(Dual::constant(-2f64).pow(Dual::variable(2.0)).deriv().is_nan());
+}
+
+#[test]
+fn constants_at_singular_points() {
+    // The partials are infinite or NaN here, but a constant has no tangent
+    // for them to act on.
+    let zero = Dual::constant(0.0);
+    for (name, y) in [
+        ("sqrt", zero.sqrt()),
+        ("cbrt", zero.cbrt()),
+        ("powf", zero.powf(0.5)),
+        ("ln", zero.ln()),
+        ("asin", Dual::constant(1.0).asin()),
+        ("hypot", zero.hypot(zero)),
+    ] {
+        assert_eq!(y.deriv(), 0.0, "{name}");
+    }
+}
+
+#[test]
+fn trigonometric() {
+    check!("sin", [-3.0, 3.0], |x| x.sin());
+    check!("cos", [-3.0, 3.0], |x| x.cos());
+    check!("tan", [-1.2, 1.2], |x| x.tan());
+    check!("asin", [-0.9, 0.9], |x| x.asin());
+    check!("acos", [-0.9, 0.9], |x| x.acos());
+    check!("atan", [-3.0, 3.0], |x| x.atan());
+    check2!("atan2", [-3.0, 3.0], [0.5, 2.0], |y, x| y.atan2(x));
+    check2!("atan2 left half-plane", [0.1, 3.0], [-2.0, -0.5], |y, x| y.atan2(x));
+}
+
+#[test]
+fn hyperbolic() {
+    check!("sinh", [-3.0, 3.0], |x| x.sinh());
+    check!("cosh", [-3.0, 3.0], |x| x.cosh());
+    check!("tanh", [-3.0, 3.0], |x| x.tanh());
+    check!("asinh", [-3.0, 3.0], |x| x.asinh());
+    check!("acosh", [1.1, 5.0], |x| x.acosh());
+    check!("atanh", [-0.9, 0.9], |x| x.atanh());
+    check!("erf", [-4.0, 4.0], |x| Ops::erf(x));
+}
+
+#[test]
+fn piecewise() {
+    check!("abs positive", [0.1, 3.0], |x| x.abs());
+    check!("abs negative", [-3.0, -0.1], |x| x.abs());
+    check!("signum", [0.1, 3.0], |x| x.signum());
+}
+
+#[test]
+fn operators() {
+    check!("neg", [-3.0, 3.0], |x| -x);
+    check!("add scalar", [-3.0, 3.0], |x| x + 2.0);
+    check!("sub scalar", [-3.0, 3.0], |x| x - 2.0);
+    check!("mul scalar", [-3.0, 3.0], |x| x * 2.0);
+    check!("div scalar", [-3.0, 3.0], |x| x / 2.0);
+    check!("scalar div", [0.2, 3.0], |x| 2.0 / x);
+    check2!("add", [-3.0, 3.0], [-2.0, 2.0], |x, y| x + y);
+    check2!("sub", [-3.0, 3.0], [-2.0, 2.0], |x, y| x - y);
+    check2!("mul", [-3.0, 3.0], [-2.0, 2.0], |x, y| x * y);
+    check2!("div", [-3.0, 3.0], [0.5, 2.0], |x, y| x / y);
+}
+
+#[test]
+fn sigmoid() {
+    check!("sigmoid", [-6.0, 6.0], |x| x.sigmoid());
+    check!("sigmoid of composite", [-2.0, 2.0], |x| (x * x.sin()).sigmoid());
+}
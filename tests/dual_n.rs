@@ -0,0 +1,41 @@
+use std::ops::{Add, Div, Mul};
+
+use dual::{Dual, DualN, Ops, Sigmoid};
+
+/// Written once against the traits, evaluated with both `Dual` and `DualN`.
+fn model<S>(x: S, y: S, z: S) -> S
+where
+    S: Copy + Sigmoid + Add<Output = S> + Mul<Output = S>,
+    f64: Div<S, Output = S>,
+{
+    (x * y.sin()).sigmoid() * z.exp() + (y * z).atan2(x).cos().sqrt()
+}
+
+#[test]
+fn gradient_matches_one_pass_per_input() {
+    let p = [0.7, -1.3, 0.4];
+    let [x, y, z] = DualN::variables(p);
+    let grad = model(x, y, z);
+
+    for i in 0..3 {
+        let seed = |j: usize| if i == j { Dual::variable(p[j]) } else { Dual::constant(p[j]) };
+        let d = model(seed(0), seed(1), seed(2));
+        assert_eq!(grad.value(), d.value());
+        assert!((grad.deriv()[i] - d.deriv()).abs() < 1e-14);
+    }
+}
+
+#[test]
+fn constants_have_zero_gradient() {
+    let x = DualN::<2>::variable(2.0, 1);
+    let c = DualN::<2>::constant(3.0);
+    let y = x * c + 1.0 / c;
+    assert_eq!(y.value(), 6.0 + 1.0 / 3.0);
+    assert_eq!(y.deriv(), [0.0, 3.0]);
+}
+
+#[test]
+fn singular_input_leaves_other_partials_alone() {
+    let [x, y] = DualN::variables([1.0, 0.0]);
+    assert_eq!((x + y.sqrt()).deriv(), [1.0, f64::INFINITY]);
+}
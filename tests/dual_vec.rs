@@ -0,0 +1,41 @@
+use dual::{DualN, DualVec, Ops, Sigmoid};
+
+#[test]
+fn gradient_matches_dual_n() {
+    let p = [0.3, 1.7, -0.8, 2.1];
+    let xs = DualVec::variables(&p);
+    let [a, b, c, d] = DualN::variables(p);
+
+    let v = ((xs[0].clone() * xs[1].clone()).sin() + xs[2].clone().exp() / xs[3].clone())
+        .sigmoid()
+        .pow(xs[3].clone());
+    let n = ((a * b).sin() + c.exp() / d).sigmoid().pow(d);
+
+    assert_eq!(v.value(), n.value());
+    for (dv, dn) in v.deriv().iter().zip(n.deriv()) {
+        assert!((dv - dn).abs() < 1e-14);
+    }
+}
+
+#[test]
+fn constants_stay_empty() {
+    let c = (DualVec::constant(2.0) * DualVec::constant(3.0)).ln() + 1.0;
+    assert!(c.is_constant());
+
+    let x = DualVec::variable(2.0, 0, 3);
+    let y = 1f64 / (c * x);
+    assert_eq!(y.deriv().len(), 3);
+    assert_eq!(y.deriv()[1], 0.0);
+}
+
+#[test]
+fn singular_input_leaves_other_partials_alone() {
+    let xs = DualVec::variables(&[1.0, 0.0]);
+    assert_eq!((xs[0].clone() + xs[1].clone().sqrt()).deriv(), [1.0, f64::INFINITY]);
+}
+
+#[test]
+#[should_panic(expected = "DualVec tangent length mismatch: 2 vs 3")]
+fn mismatched_lengths_panic() {
+    let _ = DualVec::variable(1.0, 0, 2) + DualVec::variable(1.0, 0, 3);
+}
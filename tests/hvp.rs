@@ -0,0 +1,61 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+use dual::{hvp, Sigmoid, Tape};
+
+/// Extended Rosenbrock plus a coupling term through a sigmoid and a sine.
+fn f<S>(x: &[S]) -> S
+where
+    S: Copy + Sigmoid + Add<Output = S> + Sub<Output = S> + Mul<Output = S> + Mul<f64, Output = S> + Sub<f64, Output = S>,
+    f64: Div<S, Output = S>,
+{
+    let mut acc = (x[0] * x[x.len() - 1]).sin().sigmoid();
+    for w in x.windows(2) {
+        let a = w[1] - w[0] * w[0];
+        let b = w[0] - 1.0;
+        acc = acc + a * a * 100.0 + b * b;
+    }
+    acc
+}
+
+fn gradient(x: &[f64]) -> Vec<f64> {
+    let tape = Tape::new();
+    let vars = tape.vars(x);
+    f(&vars).backward().wrt_all(&vars)
+}
+
+/// Dense Hessian from central differences of reverse-mode gradients.
+fn fd_hessian(x: &[f64]) -> Vec<Vec<f64>> {
+    let h = 1e-5;
+    (0..x.len())
+        .map(|j| {
+            let (mut xp, mut xm) = (x.to_vec(), x.to_vec());
+            xp[j] += h;
+            xm[j] -= h;
+            let (gp, gm) = (gradient(&xp), gradient(&xm));
+            gp.iter().zip(&gm).map(|(p, m)| (p - m) / (2.0 * h)).collect()
+        })
+        .collect()
+}
+
+#[test]
+fn matches_finite_difference_hessian() {
+    let x = [-1.2, 1.0, 0.3, -0.5, 0.8];
+    let h = fd_hessian(&x);
+    for v in [[1.0, 0.0, 0.0, 0.0, 0.0], [0.3, -1.0, 2.0, 0.5, -0.7], [0.0, 0.0, 0.0, 0.0, 1.0]] {
+        let hv = hvp(|x| f(x), &x, &v);
+        for (i, hvi) in hv.iter().enumerate() {
+            // `h` is symmetric, so its columns double as rows.
+            let expected: f64 = (0..x.len()).map(|j| h[j][i] * v[j]).sum();
+            assert!(
+                (hvi - expected).abs() < 1e-5 * expected.abs().max(1.0),
+                "(Hv)[{i}] = {hvi}, expected {expected}"
+            );
+        }
+    }
+}
+
+#[test]
+fn zero_direction_gives_zero() {
+    let hv = hvp(|x| f(x), &[0.5, 0.5, 0.5], &[0.0; 3]);
+    assert_eq!(hv, vec![0.0; 3]);
+}
@@ -0,0 +1,117 @@
+//! Checks second derivatives against central differences of the (already
+//! verified) first derivatives computed with `Dual`.
+
+use dual::{Dual, HyperDual, Ops, Sigmoid};
+
+mod common;
+
+use common::{grid, FiniteDiff};
+
+const FD: FiniteDiff = FiniteDiff { h: 1e-4, tol: 1e-6 };
+
+macro_rules! check {
+    ($name:expr, [$lo:expr, $hi:expr], |$x:ident| $body:expr) => {{
+        let d = |$x: Dual| -> Dual { $body };
+        let h = |$x: HyperDual| -> HyperDual { $body };
+        let d1 = |x: f64| d(Dual::variable(x)).deriv();
+        for x in grid($lo, $hi, 16) {
+            let (f, f1, f2) = HyperDual::derivatives(h, x);
+            let at = |what| format!("{} {what} at x = {x}", $name);
+            FD.assert_close(f, d(Dual::variable(x)).value(), at("value"));
+            FD.assert_close(f1, d1(x), at("f'"));
+            FD.assert_close(f2, FD.central4(d1, x), at("f''"));
+        }
+    }};
+}
+
+#[test]
+fn unary_second_derivatives() {
+    check!("exp", [-2.0, 2.0], |x| x.exp());
+    check!("ln", [0.2, 4.0], |x| x.ln());
+    check!("sin", [-3.0, 3.0], |x| x.sin());
+    check!("cos", [-3.0, 3.0], |x| x.cos());
+    check!("tan", [-1.2, 1.2], |x| x.tan());
+    check!("powi", [-2.0, 2.0], |x| x.powi(4));
+    check!("sqrt", [0.2, 4.0], |x| x.sqrt());
+    check!("cbrt", [0.2, 4.0], |x| x.cbrt());
+    check!("powf", [0.2, 3.0], |x| x.powf(-1.5));
+    check!("exp2", [-2.0, 2.0], |x| x.exp2());
+    check!("exp_m1", [-2.0, 2.0], |x| x.exp_m1());
+    check!("ln_1p", [-0.8, 3.0], |x| x.ln_1p());
+    check!("log2", [0.2, 4.0], |x| x.log2());
+    check!("log10", [0.2, 4.0], |x| x.log10());
+    check!("log", [0.2, 4.0], |x| x.log(5.0));
+    check!("asin", [-0.8, 0.8], |x| x.asin());
+    check!("acos", [-0.8, 0.8], |x| x.acos());
+    check!("atan", [-3.0, 3.0], |x| x.atan());
+    check!("sinh", [-2.0, 2.0], |x| x.sinh());
+    check!("cosh", [-2.0, 2.0], |x| x.cosh());
+    check!("tanh", [-2.0, 2.0], |x| x.tanh());
+    check!("asinh", [-2.0, 2.0], |x| x.asinh());
+    check!("acosh", [1.2, 4.0], |x| x.acosh());
+    check!("atanh", [-0.8, 0.8], |x| x.atanh());
+    check!("erf", [-2.5, 2.5], |x| Ops::erf(x));
+    check!("abs", [0.2, 2.0], |x| (-x).abs());
+    check!("sigmoid", [-4.0, 4.0], |x| x.sigmoid());
+}
+
+#[test]
+fn binary_second_derivatives() {
+    check!("mul", [-2.0, 2.0], |x| x * x.sin());
+    check!("div", [0.2, 3.0], |x| x.exp() / x);
+    check!("scalar div", [0.2, 3.0], |x| 2.0 / x);
+    check!("pow", [0.2, 3.0], |x| x.pow(x.sin()));
+    check!("atan2", [-3.0, 3.0], |x| x.atan2(x * x + 1.0));
+    check!("hypot", [-3.0, 3.0], |x| x.cos().hypot(x));
+}
+
+#[test]
+fn low_powers_at_zero() {
+    for n in 0..=3 {
+        let expected = match n {
+            0 => (1.0, 0.0, 0.0),
+            1 => (0.0, 1.0, 0.0),
+            2 => (0.0, 0.0, 2.0),
+            _ => (0.0, 0.0, 0.0),
+        };
+        assert_eq!(HyperDual::derivatives(|x| x.powi(n), 0.0), expected, "powi({n})");
+        assert_eq!(HyperDual::derivatives(|x| x.powf(n as f64), 0.0), expected, "powf({n})");
+        let pow = |x: HyperDual| x.pow(HyperDual::constant(n as f64));
+        assert_eq!(HyperDual::derivatives(pow, 0.0), expected, "pow({n})");
+    }
+}
+
+#[test]
+fn constant_exponent_on_a_negative_base() {
+    let pow = |x: HyperDual| x.pow(HyperDual::constant(3.0));
+    assert_eq!(HyperDual::derivatives(pow, -2.0), (-8.0, 12.0, -12.0));
+}
+
+#[test]
+fn singular_input_leaves_other_entries_alone() {
+    let h = HyperDual::hessian(|v| v[0] * v[0] + v[1].sqrt(), &[1.0, 0.0]);
+    assert_eq!(h, [[2.0, 0.0], [0.0, f64::NEG_INFINITY]]);
+
+    let root = HyperDual::constant(0.0).sqrt();
+    assert_eq!((root.eps1(), root.eps2(), root.eps12()), (0.0, 0.0, 0.0));
+}
+
+#[test]
+fn hessian_of_three_inputs() {
+    // f = x² y + sin(y z), so ∂²f/∂x² = 2y, ∂²f/∂x∂y = 2x, ∂²f/∂y² = -z² sin(yz),
+    // ∂²f/∂y∂z = cos(yz) - yz sin(yz), ∂²f/∂z² = -y² sin(yz).
+    let f = |v: &[HyperDual]| v[0] * v[0] * v[1] + (v[1] * v[2]).sin();
+    let (x, y, z) = (0.5, -1.2, 0.7);
+    let h = HyperDual::hessian(f, &[x, y, z]);
+    let (s, c) = ((y * z).sin(), (y * z).cos());
+    let expected = [
+        [2.0 * y, 2.0 * x, 0.0],
+        [2.0 * x, -z * z * s, c - y * z * s],
+        [0.0, c - y * z * s, -y * y * s],
+    ];
+    for i in 0..3 {
+        for j in 0..3 {
+            assert!((h[i][j] - expected[i][j]).abs() < 1e-14, "H[{i}][{j}]");
+        }
+    }
+}
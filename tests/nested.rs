@@ -0,0 +1,56 @@
+use dual::{derivative, Dual, HyperDual, Ops, Sigmoid};
+
+#[test]
+fn no_perturbation_confusion() {
+    // d/dx (x · d/dy (x y)) = d/dx x² = 2x, whatever y is.
+    for (x0, y0) in [(1.0, 1.0), (5.0, 3.0), (-2.0, 0.5)] {
+        let outer = |x: Dual<f64>| {
+            let inner = Dual::diff(|y| x * y, Dual::constant(y0));
+            x * inner
+        };
+        assert_eq!(derivative(outer, x0).1, 2.0 * x0);
+    }
+}
+
+#[test]
+fn second_derivative_matches_hyper_dual() {
+    for x0 in [-1.5, -0.2, 0.7, 2.0] {
+        let (_, d2) = derivative(
+            |x: Dual<f64>| Dual::diff(|y| (y * y.sin()).sigmoid() + 2.0 / y.exp(), x),
+            x0,
+        );
+        let (_, _, h2) = HyperDual::derivatives(|y| (y * y.sin()).sigmoid() + 2.0 / y.exp(), x0);
+        assert!((d2 - h2).abs() < 1e-13, "x = {x0}: {d2} vs {h2}");
+    }
+}
+
+#[test]
+fn forward_over_forward_hessian_vector_product() {
+    // f(a, b) = a² b + b³, H = [[2b, 2a], [2a, 6b]].
+    let f = |a: Dual<Dual<f64>>, b: Dual<Dual<f64>>| a * a * b + b.powi(3);
+    let (a, b) = (1.5, -0.5);
+    let v = [0.3, 2.0];
+    // The inner level is seeded along eᵢ and the outer one along v, so
+    // each pass gives one entry (H v)ᵢ.
+    let seed = |p: f64, ei: f64, vi: f64| Dual::new(Dual::new(p, ei), Dual::new(vi, 0.0));
+    let hv: Vec<f64> = (0..2)
+        .map(|i| {
+            let e = |j: usize| if i == j { 1.0 } else { 0.0 };
+            f(seed(a, e(0), v[0]), seed(b, e(1), v[1])).deriv().deriv()
+        })
+        .collect();
+    let expected = [2.0 * b * v[0] + 2.0 * a * v[1], 2.0 * a * v[0] + 6.0 * b * v[1]];
+    for i in 0..2 {
+        assert!((hv[i] - expected[i]).abs() < 1e-14, "(Hv)[{i}]");
+    }
+}
+
+#[test]
+fn scalars_mix_at_every_level() {
+    let x = Dual::new(Dual::variable(2.0), Dual::constant(1.0));
+    let y = (1f64 + x * 3.0 - 0.5) / 2.0 + 4f64 / x;
+    // y = (3x + 0.5) / 2 + 4 / x, y' = 3/2 - 4/x², y'' = 8/x³
+    assert_eq!(y.value().value(), 3.25 + 2.0);
+    assert_eq!(y.deriv().value(), 1.5 - 1.0);
+    assert_eq!(y.deriv().deriv(), 1.0);
+}
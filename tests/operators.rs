@@ -0,0 +1,110 @@
+use dual::{Dual, Ops};
+
+fn parts(d: Dual) -> (f64, f64) {
+    (d.value(), d.deriv())
+}
+
+#[test]
+fn scalar_on_either_side() {
+    let x = Dual::variable(2.0);
+    assert_eq!(parts(x + 3.0), (5.0, 1.0));
+    assert_eq!(parts(3.0 + x), (5.0, 1.0));
+    assert_eq!(parts(x - 3.0), (-1.0, 1.0));
+    assert_eq!(parts(3.0 - x), (1.0, -1.0));
+    assert_eq!(parts(x * 3.0), (6.0, 3.0));
+    assert_eq!(parts(3.0 * x), (6.0, 3.0));
+    assert_eq!(parts(x / 4.0), (0.5, 0.25));
+    assert_eq!(parts(4.0 / x), (2.0, -1.0));
+
+    let y = Dual::variable(2.0f32);
+    assert_eq!((3.0 - y).deriv(), -1.0);
+    assert_eq!((3.0 * y + 1.0).value(), 7.0);
+}
+
+#[test]
+fn integers_on_either_side() {
+    let x = Dual::variable(2.0);
+    assert_eq!(parts(x + 3), (5.0, 1.0));
+    assert_eq!(parts(3 - x), (1.0, -1.0));
+    assert_eq!(parts(x * 3u8), (6.0, 3.0));
+    assert_eq!(parts(3usize * x), (6.0, 3.0));
+    assert_eq!(parts(x / 4i64), (0.5, 0.25));
+    assert_eq!(parts(4u32 / x), (2.0, -1.0));
+    assert_eq!(parts(x.powi(2) - 2 * x + 1), (1.0, 2.0));
+
+    let nested = Dual::variable(Dual::variable(2.0f64));
+    // An untyped integer literal matches several impls and is resolved
+    // from context, so calling a method straight on the result needs an
+    // annotation.
+    let y: Dual<Dual> = nested * nested * 3;
+    assert_eq!(y.deriv().deriv(), 6.0);
+    let y: Dual<f32> = 2 * Dual::variable(1.5f32) - 1i16;
+    assert_eq!(y.value(), 2.0);
+}
+
+#[test]
+fn by_reference() {
+    let x = Dual::variable(2.0);
+    let c = Dual::constant(5.0);
+    let (rx, rc) = (&x, &c);
+    assert_eq!(parts(rx + rc), parts(x + c));
+    assert_eq!(parts(rx - c), parts(x - c));
+    assert_eq!(parts(x * rc), parts(x * c));
+    assert_eq!(parts(rc / rx), parts(c / x));
+    assert_eq!(parts(-rx), (-2.0, -1.0));
+
+    let (three, four, one): (&f64, &f64, &u64) = (&3.0, &4.0, &1);
+    assert_eq!(parts(rx * 3.0), (6.0, 3.0));
+    assert_eq!(parts(x - three), (-1.0, 1.0));
+    assert_eq!(parts(rx / four), (0.5, 0.25));
+    assert_eq!(parts(3.0 - rx), (1.0, -1.0));
+    assert_eq!(parts(three * x), (6.0, 3.0));
+    assert_eq!(parts(four / rx), (2.0, -1.0));
+    assert_eq!(parts(rx + 1), (3.0, 1.0));
+    assert_eq!(parts(one - rx), (-1.0, -1.0));
+
+    // Generic code over references compiles without reordering.
+    fn sum_of_squares<'a, I: IntoIterator<Item = &'a Dual>>(xs: I) -> Dual {
+        xs.into_iter().fold(Dual::constant(0.0), |acc, x| acc + x * x)
+    }
+    let xs = [Dual::variable(1.0), Dual::constant(2.0), Dual::variable(3.0)];
+    assert_eq!(parts(sum_of_squares(&xs)), (14.0, 8.0));
+}
+
+#[test]
+fn compound_assignment() {
+    let x = Dual::variable(2.0);
+    let mut y = x;
+    y += x;
+    y -= 1.0;
+    y *= &x;
+    y /= 3;
+    y += &0.5;
+    // ((2x - 1) x) / 3 + 1/2 at x = 2, derivative (4x - 1) / 3.
+    assert_eq!(parts(y), (2.5, 7.0 / 3.0));
+
+    let mut z = Dual::variable(1.5f32);
+    z *= z;
+    z -= 0.25;
+    assert_eq!((z.value(), z.deriv()), (2.0, 3.0));
+}
+
+#[test]
+fn sum_and_product() {
+    let xs: Vec<Dual> = [1.0, 2.0, 3.0, 4.0]
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| if i % 2 == 0 { Dual::variable(x) } else { Dual::constant(x) })
+        .collect();
+    assert_eq!(parts(xs.iter().sum()), (10.0, 2.0));
+    assert_eq!(parts(xs.iter().copied().sum()), (10.0, 2.0));
+    // d/dt of (1 + t)(2)(3 + t)(4) at t = 0 is 2·3·4 + 1·2·4.
+    assert_eq!(parts(xs.iter().product()), (24.0, 32.0));
+    assert_eq!(parts(xs.into_iter().product()), (24.0, 32.0));
+
+    // A zero factor leaves the other factors' contribution intact.
+    let p: Dual = [Dual::variable(0.0), Dual::constant(5.0)].iter().product();
+    assert_eq!(parts(p), (0.0, 5.0));
+    assert_eq!(parts(std::iter::empty::<Dual>().sum()), (0.0, 0.0));
+    assert_eq!(parts(std::iter::empty::<Dual>().product()), (1.0, 0.0));
+}
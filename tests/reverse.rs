@@ -0,0 +1,82 @@
+use std::ops::{Add, Div, Mul};
+
+use dual::{DualN, Ops, Sigmoid, Tape, Var};
+
+/// Written once against the traits, evaluated in forward and reverse mode.
+fn loss<S>(w: &[S], x: &[f64]) -> S
+where
+    S: Copy + Sigmoid + Add<Output = S> + Mul<f64, Output = S> + Mul<Output = S>,
+    f64: Div<S, Output = S>,
+{
+    let z = w.iter().zip(x).fold(w[0] * 0.0, |acc, (&wi, &xi)| acc + wi * xi);
+    (z.sigmoid() + w[1].tanh() * w[2]).ln()
+}
+
+#[test]
+fn gradient_matches_forward_mode() {
+    let w = [0.4, -1.1, 0.9, 2.3];
+    let x = [1.0, 0.5, -0.3, 0.8];
+
+    let tape = Tape::new();
+    let vars = tape.vars(&w);
+    let y = loss(&vars, &x);
+    let grad = y.backward().wrt_all(&vars);
+
+    let forward = loss(&DualN::variables(w), &x);
+    assert_eq!(y.value(), forward.value());
+    for (r, f) in grad.iter().zip(forward.deriv()) {
+        assert!((r - f).abs() < 1e-14);
+    }
+}
+
+#[test]
+fn constants_are_not_recorded() {
+    let tape = Tape::new();
+    let x = tape.var(3.0);
+    let c = (Var::constant(2.0) * Var::constant(4.0)).exp();
+    assert_eq!(tape.len(), 1);
+
+    let y = x * c + 1.0;
+    let grad = y.backward();
+    assert_eq!(grad.wrt(&x), 8f64.exp());
+    assert_eq!(grad.wrt(&c), 0.0);
+}
+
+#[test]
+fn unused_leaves_get_zero() {
+    let tape = Tape::new();
+    let vars = tape.vars(&[1.0, 2.0, 3.0]);
+    let y = vars[0] * vars[2];
+    assert_eq!(y.backward().wrt_all(&vars), vec![3.0, 0.0, 1.0]);
+}
+
+#[test]
+#[should_panic(expected = "different tapes")]
+fn mixing_tapes_panics() {
+    let (a, b) = (Tape::new(), Tape::new());
+    let _ = a.var(1.0) + b.var(2.0);
+}
+
+#[test]
+#[should_panic(expected = "different tape")]
+fn gradient_wrt_another_tape_panics() {
+    let (a, b) = (Tape::new(), Tape::new());
+    let x = a.var(1.0);
+    let y = b.var(2.0);
+    let grad = (x * x).backward();
+    assert_eq!(grad.wrt(&x), 2.0);
+    grad.wrt(&y);
+}
+
+#[test]
+#[should_panic(expected = "different tape")]
+fn gradient_wrt_a_tape_created_later_panics() {
+    // The second tape may well reuse the first one's address.
+    let grad = {
+        let tape = Tape::new();
+        let x = tape.var(1.0);
+        (x * x).backward()
+    };
+    let tape = Tape::new();
+    grad.wrt(&tape.var(1.0));
+}
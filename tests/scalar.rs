@@ -0,0 +1,90 @@
+use std::ops::Sub;
+
+use dual::{Dual, DualN, DualVec, HyperDual, Scalar, SparseDual, Tape, Taylor, TraceTape, Var};
+
+/// `f(x) = 3 sin x + x² / 2 - 1`, written once.
+fn model<S: Scalar<T>, T>(x: S) -> S {
+    x.clone().sin() * S::from_f64(3.0) + x.clone() * x / S::from_f64(2.0) - S::one()
+}
+
+/// The same model with plain `f64` literals on both sides.
+fn literals<S: Scalar>(x: S) -> S
+where
+    f64: Sub<S, Output = S>,
+{
+    x.clone().sin() * 3.0 + x.clone() * x / 2.0 - (2.0 - S::one())
+}
+
+fn value(x: f64) -> f64 {
+    3.0 * x.sin() + x * x / 2.0 - 1.0
+}
+
+fn slope(x: f64) -> f64 {
+    3.0 * x.cos() + x
+}
+
+#[test]
+fn plain_floats() {
+    assert_eq!(model::<f64, f64>(0.7), value(0.7));
+    assert_eq!(literals(0.7), value(0.7));
+    assert!((model::<f32, f32>(0.7) - value(0.7) as f32).abs() < 1e-6);
+}
+
+#[test]
+fn every_dual_type() {
+    let x = 0.7;
+    let d = literals(Dual::variable(x));
+    assert_eq!((d.value(), d.deriv()), (value(x), slope(x)));
+    assert_eq!(model::<_, f32>(Dual::variable(x as f32)).deriv(), slope(x) as f32);
+    assert_eq!(literals(DualN::<2>::variable(x, 1)).deriv(), [0.0, slope(x)]);
+    assert_eq!(literals(DualVec::variable(x, 0, 1)).deriv(), &[slope(x)]);
+    assert_eq!(literals(SparseDual::variable(x, 4)).partial(4), slope(x));
+    assert_eq!(literals(HyperDual::variable(x)).eps1(), slope(x));
+    assert_eq!(literals(Taylor::<2>::variable(x)).derivative(2), 1.0 - 3.0 * x.sin());
+    let nested = literals(Dual::variable(Dual::variable(x)));
+    assert_eq!(nested.deriv().deriv(), 1.0 - 3.0 * x.sin());
+}
+
+#[test]
+fn reverse_mode_and_tracers() {
+    let tape = Tape::new();
+    let x = tape.var(0.7);
+    let y = literals(x);
+    assert_eq!(y.value(), value(0.7));
+    assert_eq!(y.backward().wrt(&x), slope(0.7));
+
+    let tape = TraceTape::new(2);
+    let x = tape.inputs();
+    assert_eq!(tape.deps(&literals(x[1])), vec![1]);
+}
+
+#[test]
+fn scalars_on_the_left() {
+    let x = Dual::variable(2.0);
+    for (y, dy) in [(3f64 + x, 1.0), (3f64 - x, -1.0), (3f64 * x, 3.0), (3f64 / x, -0.75)] {
+        assert_eq!(y.deriv(), dy);
+    }
+    let x = DualVec::variable(2.0, 0, 1);
+    assert_eq!((3f64 - x.clone()).deriv(), &[-1.0]);
+    assert_eq!((3f64 * x).deriv(), &[3.0]);
+    let x = SparseDual::variable(2.0f32, 1);
+    assert_eq!((3f32 - x).partial(1), -1.0);
+}
+
+fn is_zero<S: Scalar>(x: &S) -> bool {
+    x.is_zero()
+}
+
+#[test]
+fn is_zero_looks_at_every_part() {
+    assert!(is_zero(&-0.0));
+    assert!(is_zero(&Dual::constant(0.0)));
+    assert!(!is_zero(&Dual::new(0.0, 1.0)));
+    assert!(!is_zero(&Dual::new(Dual::new(0.0, 0.0), Dual::new(0.0, 1.0))));
+    assert!(is_zero(&DualVec::constant(0.0)));
+    assert!(!is_zero(&SparseDual::variable(0.0, 3)));
+
+    let tape = Tape::new();
+    assert!(is_zero(&Var::constant(0.0)));
+    assert!(!is_zero(&tape.var(0.0)));
+}
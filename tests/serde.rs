@@ -0,0 +1,123 @@
+#![cfg(feature = "serde")]
+
+use dual::{Dual, DualN, DualVec, HyperDual, Matrix, SparseDual, SparseMatrix, Sparsity, Taylor};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Round-trips `v` through JSON and through bincode.
+fn round_trip<V: Serialize + DeserializeOwned>(v: &V) -> (V, V) {
+    let json = serde_json::to_string(v).unwrap();
+    let bytes = bincode::serialize(v).unwrap();
+    (serde_json::from_str(&json).unwrap(), bincode::deserialize(&bytes).unwrap())
+}
+
+#[test]
+fn dual_layout_is_a_struct_of_value_and_tangent() {
+    let x = Dual::new(1.5, -2.0);
+    assert_eq!(serde_json::to_string(&x).unwrap(), r#"{"x":1.5,"dx":-2.0}"#);
+    assert_eq!(bincode::serialize(&x).unwrap().len(), 16);
+
+    let y: Dual = serde_json::from_str(r#"{ "dx": 4, "x": 3 }"#).unwrap();
+    assert!(y.eq_full(&Dual::new(3.0, 4.0)));
+    assert!(serde_json::from_str::<Dual>(r#"{"x":1.0}"#).is_err());
+}
+
+#[test]
+fn forward_types_round_trip_exactly() {
+    let x = Dual::new(0.1, f64::MIN_POSITIVE);
+    for y in <[Dual; 2]>::from(round_trip(&x)) {
+        assert!(y.eq_full(&x));
+    }
+
+    let nested: Dual<Dual<f32>> = Dual::new(Dual::new(1.0, 2.0), Dual::new(3.0, 4.0));
+    assert_eq!(
+        serde_json::to_string(&nested).unwrap(),
+        r#"{"x":{"x":1.0,"dx":2.0},"dx":{"x":3.0,"dx":4.0}}"#
+    );
+    for y in <[Dual<Dual<f32>>; 2]>::from(round_trip(&nested)) {
+        assert!(y.eq_full(&nested));
+    }
+
+    let n = DualN::new(1.0, [0.5, -0.25, 8.0]);
+    assert_eq!(serde_json::to_string(&n).unwrap(), r#"{"x":1.0,"dx":[0.5,-0.25,8.0]}"#);
+    assert_eq!(bincode::serialize(&n).unwrap().len(), 32);
+    for y in <[DualN<3>; 2]>::from(round_trip(&n)) {
+        assert_eq!((y.value(), y.deriv()), (n.value(), n.deriv()));
+    }
+
+    let v = DualVec::new(2.0, vec![1.0, 0.0, -1.0]);
+    for y in <[DualVec; 2]>::from(round_trip(&v)) {
+        assert_eq!((y.value(), y.deriv()), (v.value(), v.deriv()));
+    }
+
+    let s = SparseDual::new(2.0, vec![(7, 1.0), (2, -3.0)]);
+    for y in <[SparseDual; 2]>::from(round_trip(&s)) {
+        assert_eq!((y.value(), y.deriv()), (s.value(), s.deriv()));
+    }
+
+    let h = HyperDual::new(1.0, 2.0, 3.0, 4.0);
+    assert_eq!(
+        serde_json::to_string(&h).unwrap(),
+        r#"{"x":1.0,"e1":2.0,"e2":3.0,"e12":4.0}"#
+    );
+    for y in <[HyperDual; 2]>::from(round_trip(&h)) {
+        assert_eq!((y.value(), y.eps1(), y.eps2(), y.eps12()), (1.0, 2.0, 3.0, 4.0));
+    }
+
+    let t = Taylor::new(1.0, [1.0; 40]);
+    for y in <[Taylor<40>; 2]>::from(round_trip(&t)) {
+        assert_eq!(y.value(), 1.0);
+        assert!((1..=40).all(|k| y.coeff(k) == 1.0));
+    }
+}
+
+#[test]
+fn fixed_length_tangents_reject_the_wrong_length() {
+    assert!(serde_json::from_str::<DualN<3>>(r#"{"x":1.0,"dx":[1.0,2.0]}"#).is_err());
+    assert!(serde_json::from_str::<DualN<3>>(r#"{"x":1.0,"dx":[1.0,2.0,3.0,4.0]}"#).is_err());
+    assert!(serde_json::from_str::<Taylor<2>>(r#"{"x":1.0,"c":[1.0,2.0]}"#).is_ok());
+}
+
+#[test]
+fn sparse_dual_is_normalized_on_the_way_in() {
+    let s: SparseDual = serde_json::from_str(r#"{"x":0.0,"dx":[[5,1.0],[1,2.0],[5,3.0]]}"#).unwrap();
+    assert_eq!(s.deriv(), &[(1, 2.0), (5, 4.0)]);
+}
+
+#[test]
+fn matrices_round_trip_and_reject_bad_shapes() {
+    let m = Matrix::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+    assert_eq!(
+        serde_json::to_string(&m).unwrap(),
+        r#"{"rows":2,"cols":3,"data":[1.0,2.0,3.0,4.0,5.0,6.0]}"#
+    );
+    for y in <[Matrix; 2]>::from(round_trip(&m)) {
+        assert_eq!(y, m);
+    }
+    assert!(serde_json::from_str::<Matrix>(r#"{"rows":2,"cols":3,"data":[1.0]}"#).is_err());
+
+    let pattern = Sparsity::from_rows(3, &[vec![2, 0], vec![], vec![1]]);
+    for y in <[Sparsity; 2]>::from(round_trip(&pattern)) {
+        assert_eq!(y, pattern);
+    }
+    for bad in [
+        r#"{"rows":2,"cols":3,"row_ptr":[0,1],"col_idx":[0]}"#,
+        r#"{"rows":1,"cols":3,"row_ptr":[0,2],"col_idx":[2,1]}"#,
+        r#"{"rows":1,"cols":3,"row_ptr":[0,1],"col_idx":[3]}"#,
+        r#"{"rows":2,"cols":3,"row_ptr":[0,2,1],"col_idx":[0]}"#,
+    ] {
+        assert!(serde_json::from_str::<Sparsity>(bad).is_err(), "{bad}");
+    }
+
+    let sparse: SparseMatrix = serde_json::from_str(&format!(
+        r#"{{"pattern":{},"values":[1.0,2.0,3.0]}}"#,
+        serde_json::to_string(&pattern).unwrap()
+    ))
+    .unwrap();
+    assert_eq!(sparse.get(0, 2), 2.0);
+    for y in <[SparseMatrix; 2]>::from(round_trip(&sparse)) {
+        assert_eq!(y, sparse);
+    }
+    let short = format!(r#"{{"pattern":{},"values":[1.0]}}"#, serde_json::to_string(&pattern).unwrap());
+    assert!(serde_json::from_str::<SparseMatrix>(&short).is_err());
+}
@@ -0,0 +1,100 @@
+use std::f64::consts::LN_2;
+
+use dual::{cross_entropy, log_softmax, logsumexp, nll, softmax, Dual, DualN, DualVec, SparseDual, Tape};
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-12, "got {actual}, expected {expected}");
+}
+
+#[test]
+fn large_magnitudes() {
+    assert_close(logsumexp(&[1e3, 1e3]), 1e3 + LN_2);
+    assert_close(logsumexp(&[-1e3, -1e3]), -1e3 + LN_2);
+    assert_eq!(softmax(&[1e3, 0.0, -1e3]), vec![1.0, (-1e3f64).exp(), 0.0]);
+    let ls = log_softmax(&[1e3, 0.0, -1e3]);
+    assert_eq!(ls, vec![0.0, -1e3, -2e3]);
+    assert_eq!(logsumexp::<f64>(&[]), f64::NEG_INFINITY);
+    assert_eq!(logsumexp(&[f64::NEG_INFINITY, 0.0]), 0.0);
+    assert_eq!(logsumexp(&[f64::INFINITY, 0.0]), f64::INFINITY);
+    assert!((logsumexp(&[1e3f32, 1e3]) - (1e3 + LN_2 as f32)).abs() < 1e-3);
+}
+
+#[test]
+fn infinite_logits() {
+    let inf = f64::INFINITY;
+    assert_eq!(softmax(&[inf, 1.0]), vec![1.0, 0.0]);
+    assert_eq!(softmax(&[inf, -inf, inf]), vec![0.5, 0.0, 0.5]);
+    assert_eq!(log_softmax(&[inf, 1.0]), vec![0.0, -inf]);
+    assert_eq!(log_softmax(&[1.0, inf, inf]), vec![-inf, -LN_2, -LN_2]);
+    assert_eq!(cross_entropy(&[inf, 1.0], 0), 0.0);
+    assert_eq!(cross_entropy(&[inf, 1.0], 1), inf);
+
+    let y = softmax(&[Dual::constant(inf), Dual::variable(1.0)]);
+    assert_eq!((y[0].value(), y[0].deriv(), y[1].value(), y[1].deriv()), (1.0, 0.0, 0.0, 0.0));
+}
+
+#[test]
+fn softmax_jacobian() {
+    let x = [0.3, -1.2, 2.0, 0.7];
+    let s = softmax(&x);
+    assert_close(s.iter().sum(), 1.0);
+    let y = softmax(&DualN::variables(x));
+    for i in 0..4 {
+        assert_close(y[i].value(), s[i]);
+        for j in 0..4 {
+            let delta = if i == j { 1.0 } else { 0.0 };
+            assert_close(y[i].deriv()[j], s[i] * (delta - s[j]));
+        }
+    }
+    // The gradient of logsumexp is softmax, at any shift.
+    for shift in [0.0, 800.0, -800.0] {
+        let shifted = x.map(|xi| xi + shift);
+        let g = logsumexp(&DualN::variables(shifted)).deriv();
+        for i in 0..4 {
+            assert_close(g[i], s[i]);
+        }
+    }
+}
+
+#[test]
+fn tangent_types_agree() {
+    let x = [1.5, -0.5, 0.25];
+    let dense = log_softmax(&DualN::variables(x));
+    let vec = log_softmax(&DualVec::variables(&x));
+    let sparse = log_softmax(&SparseDual::variables(&x));
+    for i in 0..3 {
+        assert_eq!(dense[i].deriv(), vec[i].deriv());
+        for j in 0..3 {
+            assert_eq!(dense[i].deriv()[j], sparse[i].partial(j));
+        }
+    }
+    let d = softmax(&[Dual::variable(x[0]), Dual::constant(x[1]), Dual::constant(x[2])]);
+    let n = softmax(&DualN::variables(x));
+    for i in 0..3 {
+        assert_close(d[i].deriv(), n[i].deriv()[0]);
+    }
+}
+
+#[test]
+fn losses() {
+    let logits = [2.0, -1.0, 0.5];
+    let target = 2;
+    let ce = cross_entropy(&logits, target);
+    assert_close(ce, -softmax(&logits)[target].ln());
+    assert_close(nll(&log_softmax(&logits), target), ce);
+
+    // d CE / d logits = softmax - onehot, in both modes.
+    let s = softmax(&logits);
+    let forward = cross_entropy(&DualN::variables(logits), target).deriv();
+    let tape = Tape::new();
+    let vars = tape.vars(&logits);
+    let reverse = cross_entropy(&vars, target).backward().wrt_all(&vars);
+    for i in 0..3 {
+        let expected = s[i] - if i == target { 1.0 } else { 0.0 };
+        assert_close(forward[i], expected);
+        assert_close(reverse[i], expected);
+    }
+
+    // Confident and wrong stays finite.
+    assert_close(cross_entropy(&[1e3, -1e3], 1), 2e3);
+}
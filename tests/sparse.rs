@@ -0,0 +1,45 @@
+use std::ops::{Add, Mul, Sub};
+
+use dual::{DualVec, Ops, SparseDual};
+
+/// A chain residual: output `k` only touches inputs `k - 1`, `k` and `k + 1`.
+fn residuals<S>(x: &[S]) -> Vec<S>
+where
+    S: Clone + Ops + Add<f64, Output = S> + Sub<Output = S> + Mul<Output = S>,
+{
+    let n = x.len();
+    (0..n)
+        .map(|k| {
+            let left = x[k.saturating_sub(1)].clone();
+            let right = x[(k + 1).min(n - 1)].clone().exp();
+            x[k].clone().sin() * x[k].clone() - left * right + 1.0
+        })
+        .collect()
+}
+
+#[test]
+fn jacobian_rows_are_sparse_and_match_dense() {
+    let p: Vec<f64> = (0..50).map(|i| 0.1 * i as f64 - 2.0).collect();
+    let sparse = residuals(&SparseDual::variables(&p));
+    let dense = residuals(&DualVec::variables(&p));
+
+    for (k, (s, d)) in sparse.iter().zip(&dense).enumerate() {
+        assert!(s.deriv().len() <= 3);
+        assert!(s.deriv().windows(2).all(|w| w[0].0 < w[1].0));
+        for (i, &di) in d.deriv().iter().enumerate() {
+            assert_eq!(s.partial(i), di, "row {k}, column {i}");
+        }
+    }
+}
+
+#[test]
+fn new_sorts_and_sums_duplicates() {
+    let x = SparseDual::new(1.0, vec![(4, 1.0), (1, 2.0), (4, 0.5)]);
+    assert_eq!(x.deriv(), &[(1, 2.0), (4, 1.5)]);
+}
+
+#[test]
+fn stored_zero_entries_stay_zero() {
+    let y = SparseDual::new(0.0, vec![(0, 0.0), (1, 1.0)]).sqrt();
+    assert_eq!(y.deriv(), &[(0, 0.0), (1, f64::INFINITY)]);
+}
@@ -0,0 +1,74 @@
+use dual::{jacobian, jacobian_sparsity, sparse_jacobian, Real, SparseJacobian, Sparsity, VectorFunction};
+
+/// Discretized 1-D reaction-diffusion residual: row `i` touches `i - 1`,
+/// `i` and `i + 1`, plus a global coupling to input 0 in the last row.
+struct Residual;
+
+impl VectorFunction for Residual {
+    fn eval<T: Real>(&self, u: &[T]) -> Vec<T> {
+        let n = u.len();
+        let two = T::from_f64(2.0);
+        let mut r: Vec<T> = (0..n)
+            .map(|i| {
+                let left = if i > 0 { u[i - 1] } else { T::zero() };
+                let right = if i + 1 < n { u[i + 1] } else { T::zero() };
+                left - two * u[i] + right + u[i].sin() * u[i].exp()
+            })
+            .collect();
+        r[n - 1] = r[n - 1] * u[0].tanh();
+        r
+    }
+}
+
+#[test]
+fn detects_banded_pattern() {
+    let pattern = jacobian_sparsity(&Residual, 6);
+    assert_eq!(pattern.row(0), &[0, 1]);
+    assert_eq!(pattern.row(3), &[2, 3, 4]);
+    assert_eq!(pattern.row(5), &[0, 4, 5]);
+    assert_eq!(pattern.nnz(), 2 + 3 * 4 + 3);
+}
+
+#[test]
+fn coloring_is_valid_and_compact() {
+    let plan = SparseJacobian::new(&Residual, 200);
+    let colors = plan.colors();
+    for i in 0..plan.pattern().rows() {
+        let mut seen: Vec<usize> = plan.pattern().row(i).iter().map(|&j| colors[j]).collect();
+        seen.sort_unstable();
+        seen.dedup();
+        assert_eq!(seen.len(), plan.pattern().row(i).len(), "row {i} repeats a color");
+    }
+    assert!(colors.iter().max().unwrap() < &5);
+    assert_eq!(plan.passes(), 1);
+}
+
+#[test]
+fn matches_dense_jacobian() {
+    let x: Vec<f64> = (0..40).map(|i| (i as f64 * 0.37).sin()).collect();
+    let sparse = sparse_jacobian(&Residual, &x);
+    let dense = jacobian(&Residual, &x);
+    assert_eq!(sparse.to_dense(), dense);
+    for (i, j, v) in sparse.to_coo() {
+        assert_eq!(v, dense[(i, j)]);
+        assert_eq!(sparse.get(i, j), v);
+    }
+}
+
+#[test]
+fn many_colors_take_several_passes() {
+    // A dense 20 × 20 pattern needs one color per column.
+    let rows = vec![(0..20).collect::<Vec<_>>(); 20];
+    let plan = SparseJacobian::from_pattern(Sparsity::from_rows(20, &rows));
+    assert_eq!(plan.passes(), 3);
+
+    struct Dense;
+    impl VectorFunction for Dense {
+        fn eval<T: Real>(&self, x: &[T]) -> Vec<T> {
+            let s = x.iter().fold(T::zero(), |acc, &xi| acc + xi * xi);
+            x.iter().map(|&xi| xi * s).collect()
+        }
+    }
+    let x: Vec<f64> = (0..20).map(|i| i as f64 / 10.0).collect();
+    assert_eq!(plan.eval(&Dense, &x).to_dense(), jacobian(&Dense, &x));
+}
@@ -0,0 +1,115 @@
+//! Checks each order of `Taylor` against a central difference of the order
+//! below it, and order 1 and 2 against `Dual` and `HyperDual`.
+
+use dual::{Dual, HyperDual, Ops, Sigmoid, Taylor};
+
+mod common;
+
+use common::{grid, FiniteDiff};
+
+const FD: FiniteDiff = FiniteDiff { h: 1e-4, tol: 1e-6 };
+
+macro_rules! check {
+    ($name:expr, [$lo:expr, $hi:expr], |$x:ident| $body:expr) => {{
+        let t = |$x: Taylor<4>| -> Taylor<4> { $body };
+        let t1 = |$x: Taylor<1>| -> Taylor<1> { $body };
+        let d = |$x: Dual| -> Dual { $body };
+        let h = |$x: HyperDual| -> HyperDual { $body };
+        let nth = |k: usize, x: f64| t(Taylor::variable(x)).derivative(k);
+        for x in grid($lo, $hi, 12) {
+            let (_, f1, f2) = HyperDual::derivatives(h, x);
+            let dual = d(Dual::variable(x));
+            let jet = t1(Taylor::variable(x));
+            let at = |what| format!("{} {what} at x = {x}", $name);
+            FD.assert_close(jet.value(), dual.value(), at("K=1 value"));
+            FD.assert_close(jet.deriv(), dual.deriv(), at("K=1 deriv"));
+            FD.assert_close(nth(1, x), f1, at("f'"));
+            FD.assert_close(nth(2, x), f2, at("f''"));
+            for k in 3..=4 {
+                let fd = FD.central4(|x| nth(k - 1, x), x);
+                FD.assert_close(nth(k, x), fd, at("higher order"));
+            }
+        }
+    }};
+}
+
+#[test]
+fn elementary_functions() {
+    check!("exp", [-2.0, 2.0], |x| x.exp());
+    check!("ln", [0.3, 4.0], |x| x.ln());
+    check!("sin", [-3.0, 3.0], |x| x.sin());
+    check!("cos", [-3.0, 3.0], |x| x.cos());
+    check!("tan", [-1.0, 1.0], |x| x.tan());
+    check!("powi", [-2.0, 2.0], |x| x.powi(5));
+    check!("powi negative", [0.5, 2.0], |x| x.powi(-3));
+    check!("sqrt", [0.3, 4.0], |x| x.sqrt());
+    check!("cbrt", [-4.0, -0.3], |x| x.cbrt());
+    check!("powf", [0.3, 3.0], |x| x.powf(2.5));
+    check!("pow", [0.3, 3.0], |x| x.pow(x.cos()));
+    check!("exp2", [-2.0, 2.0], |x| x.exp2());
+    check!("exp_m1", [-2.0, 2.0], |x| x.exp_m1());
+    check!("ln_1p", [-0.7, 3.0], |x| x.ln_1p());
+    check!("log2", [0.3, 4.0], |x| x.log2());
+    check!("log10", [0.3, 4.0], |x| x.log10());
+    check!("log", [0.3, 4.0], |x| x.log(7.0));
+    check!("asin", [-0.7, 0.7], |x| x.asin());
+    check!("acos", [-0.7, 0.7], |x| x.acos());
+    check!("atan", [-3.0, 3.0], |x| x.atan());
+    check!("atan2", [-3.0, 3.0], |x| x.atan2(x * x + 1.0));
+    check!("sinh", [-2.0, 2.0], |x| x.sinh());
+    check!("cosh", [-2.0, 2.0], |x| x.cosh());
+    check!("tanh", [-2.0, 2.0], |x| x.tanh());
+    check!("asinh", [-2.0, 2.0], |x| x.asinh());
+    check!("acosh", [1.3, 4.0], |x| x.acosh());
+    check!("atanh", [-0.7, 0.7], |x| x.atanh());
+    check!("erf", [-2.0, 2.0], |x| Ops::erf(x));
+    check!("hypot", [0.3, 3.0], |x| x.sin().hypot(x));
+    check!("abs", [-3.0, -0.3], |x| x.abs());
+    check!("div", [0.3, 3.0], |x| x.sin() / x);
+    check!("sigmoid", [-4.0, 4.0], |x| x.sigmoid());
+}
+
+#[test]
+fn exp_coefficients_are_reciprocal_factorials() {
+    let e = Taylor::<6>::variable(0.0).exp();
+    let mut factorial = 1.0;
+    for k in 0..=6 {
+        factorial *= k.max(1) as f64;
+        assert!((e.coeff(k) - 1.0 / factorial).abs() < 1e-15);
+    }
+}
+
+#[test]
+fn powers_at_zero_match_dual() {
+    let same = |a: f64, b: f64| a == b || (a.is_nan() && b.is_nan());
+    for n in [0.0, 1.0, 2.0, 3.0, 0.5, 2.5, -1.0] {
+        let jet = Taylor::<1>::variable(0.0).powf(n);
+        let dual = Dual::variable(0.0).powf(n);
+        assert!(same(jet.value(), dual.value()) && same(jet.deriv(), dual.deriv()), "powf({n})");
+    }
+    let (jet, dual) = (Taylor::<1>::variable(0.0).cbrt(), Dual::variable(0.0).cbrt());
+    assert_eq!((jet.value(), jet.deriv()), (dual.value(), dual.deriv()));
+
+    let square = Taylor::<3>::variable(0.0).powf(2.0);
+    assert_eq!((1..=3).map(|k| square.coeff(k)).collect::<Vec<_>>(), [0.0, 1.0, 0.0]);
+    let root = Taylor::<2>::variable(0.0).powf(1.5);
+    assert_eq!((root.coeff(1), root.coeff(2)), (0.0, f64::INFINITY));
+    assert_eq!(Taylor::<1>::constant(0.0).powf(0.5).deriv(), 0.0);
+}
+
+#[test]
+fn constants_at_singular_points() {
+    let zero = Taylor::<3>::constant(0.0);
+    for (name, y) in [
+        ("sqrt", zero.sqrt()),
+        ("cbrt", zero.cbrt()),
+        ("powf", zero.powf(0.5)),
+        ("ln", zero.ln()),
+        ("pow", zero.pow(Taylor::constant(2.0))),
+        ("asin", Taylor::constant(1.0).asin()),
+        ("hypot", zero.hypot(zero)),
+        ("atan2", zero.atan2(zero)),
+    ] {
+        assert!((1..=3).all(|k| y.coeff(k) == 0.0), "{name}");
+    }
+}
@@ -0,0 +1,85 @@
+use std::error::Error;
+
+use dual::{Dual, Ops, ParseDualError};
+
+#[test]
+fn display_honors_precision_and_exponent() {
+    let y = Dual::variable(1.0).sin();
+    assert_eq!(format!("{y:.10}"), "0.8414709848 + 0.5403023059ε");
+    assert_eq!(format!("{y:.3e}"), "8.415e-1 + 5.403e-1ε");
+    assert_eq!(Dual::new(1.5, -2.0).to_string(), "1.5 - 2ε");
+    assert_eq!(format!("{:.2}", Dual::new(-0.5, 0.25)), "-0.50 + 0.25ε");
+    assert_eq!(Dual::new(0.0, -0.0).to_string(), "0 - 0ε");
+    assert_eq!(Dual::new(f64::INFINITY, f64::NAN).to_string(), "inf + NaNε");
+    assert_eq!(format!("{:e}", Dual::new(1e-7f32, 3e8)), "1e-7 + 3e8ε");
+}
+
+#[test]
+fn width_and_sign_apply_once() {
+    let d = Dual::new(1.0, 2.0);
+    assert_eq!(format!("{d:>14.1}"), "    1.0 + 2.0ε");
+    assert_eq!(format!("{d:14.1}"), "    1.0 + 2.0ε");
+    assert_eq!(format!("{d:<9}"), "1 + 2ε   ");
+    assert_eq!(format!("{d:*^10}"), "**1 + 2ε**");
+    assert_eq!(format!("{d:3}"), "1 + 2ε");
+    assert_eq!(format!("{d:+}"), "+1 + 2ε");
+    assert_eq!(format!("{:+.1}", Dual::new(-1.0, -2.0)), "-1.0 - 2.0ε");
+    assert_eq!(format!("{:>+18.1e}", Dual::new(150.0, 0.5)), "  +1.5e2 + 5.0e-1ε");
+}
+
+#[test]
+fn zero_flag_pads_after_the_sign() {
+    assert_eq!(format!("{:020.2}", Dual::new(-1.0, 2.0)), "-00000001.00 + 2.00ε");
+    assert_eq!(format!("{:+08}", Dual::new(1.0, -2.0)), "+01 - 2ε");
+    assert_eq!(format!("{:08}", Dual::new(1.0, 2.0)), "001 + 2ε");
+    assert_eq!(format!("{:03}", Dual::new(1.0, 2.0)), "1 + 2ε");
+}
+
+#[test]
+fn round_trips_exactly() {
+    let values = [
+        Dual::variable(1.0).sin(),
+        Dual::variable(0.1).exp(),
+        Dual::new(-1e-300, 7e250),
+        Dual::new(-0.0, -0.0),
+        Dual::new(f64::NEG_INFINITY, -f64::INFINITY),
+        Dual::new(f64::MIN_POSITIVE / 8.0, -f64::MAX),
+    ];
+    for d in values {
+        for text in [d.to_string(), format!("{d:e}")] {
+            let back: Dual = text.parse().unwrap();
+            assert_eq!(back.value().to_bits(), d.value().to_bits(), "{text}");
+            assert_eq!(back.deriv().to_bits(), d.deriv().to_bits(), "{text}");
+        }
+    }
+    let d = Dual::variable(0.3f32).tanh();
+    let back: Dual<f32> = d.to_string().parse().unwrap();
+    assert!(back.eq_full(&d));
+}
+
+#[test]
+fn parses_loose_notation() {
+    let parse = |s: &str| s.parse::<Dual>().map(|d| (d.value(), d.deriv()));
+    assert_eq!(parse("1+2ε"), Ok((1.0, 2.0)));
+    assert_eq!(parse("  -1.5e-3 -  4E+2ε "), Ok((-1.5e-3, -400.0)));
+    assert_eq!(parse("3 + -2ε"), Ok((3.0, -2.0)));
+    assert_eq!(parse("1 + +2ε"), Ok((1.0, 2.0)));
+    assert_eq!(parse("1 - - 2ε"), Ok((1.0, 2.0)));
+    assert_eq!(parse("1 + + 2ε"), Ok((1.0, 2.0)));
+    assert_eq!(parse("1 - -2ε"), Ok((1.0, 2.0)));
+    assert_eq!(parse("- 1 + 2ε"), Ok((-1.0, 2.0)));
+    assert_eq!(parse("2.5"), Ok((2.5, 0.0)));
+    let nan = "NaN + 1ε".parse::<Dual>().unwrap();
+    assert!(nan.value().is_nan());
+}
+
+#[test]
+fn rejects_malformed_text() {
+    for bad in ["", "2ε", "1 + ε", "1 + 2", "x + 1ε", "1 + 2ε + 3ε", "1 * 2ε", "1 - - -2ε", "--1 + 2ε"] {
+        let err: ParseDualError = bad.parse::<Dual>().unwrap_err();
+        assert!(!err.to_string().is_empty(), "{bad}");
+    }
+    let err = "1 + twoε".parse::<Dual>().unwrap_err();
+    assert!(err.source().is_some());
+    assert!("2ε".parse::<Dual>().unwrap_err().source().is_none());
+}
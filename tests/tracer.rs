@@ -0,0 +1,67 @@
+use dual::{hessian, hessian_sparsity, Function, HessianTape, Ops, Real, Sigmoid, TraceTape};
+
+#[test]
+fn tracer_records_dependencies() {
+    let tape = TraceTape::new(4);
+    let x = tape.inputs();
+    let y = (x[0] * 2.0).sin() + x[2].sigmoid();
+    let z = 1f64 / (x[1] - 3.0);
+    let w = x[3].powi(0) + x[3].signum();
+    assert_eq!(tape.deps(&y), vec![0, 2]);
+    assert_eq!(tape.deps(&z), vec![1]);
+    assert!(tape.deps(&w).is_empty());
+    assert_eq!(tape.deps(&(y * z).atan2(x[3])), vec![0, 1, 2, 3]);
+}
+
+#[test]
+fn hessian_tracer_separates_linear_and_nonlinear() {
+    let tape = HessianTape::new(4);
+    let x = tape.inputs();
+    // Linear: gradient only.
+    let lin = x[0] * 3.0 - x[1] + x[2].abs();
+    assert_eq!(tape.deps(&lin), vec![0, 1, 2]);
+    assert!(tape.hessian(&lin).is_empty());
+    // A product couples its operands but not each with itself.
+    assert_eq!(tape.hessian(&(x[0] * x[1])), vec![(0, 1)]);
+    // A quotient is also nonlinear in its denominator.
+    assert_eq!(tape.hessian(&(x[0] / x[1])), vec![(0, 1), (1, 1)]);
+    assert_eq!(tape.hessian(&(x[0] / 2.0)), vec![]);
+    assert_eq!(tape.hessian(&(1f64 / x[0])), vec![(0, 0)]);
+    // Nonlinear unary functions couple everything underneath.
+    assert_eq!(tape.hessian(&(x[0] + x[3]).exp()), vec![(0, 0), (0, 3), (3, 3)]);
+    assert_eq!(tape.hessian(&x[2].powi(1)), vec![]);
+    assert_eq!(tape.hessian(&x[2].powf(2.5)), vec![(2, 2)]);
+    assert_eq!(tape.hessian(&x[1].hypot(x[2])), vec![(1, 1), (1, 2), (2, 2)]);
+}
+
+/// Chained quartic: tridiagonal Hessian, plus a
+/// coupling between the first and last inputs.
+struct Chain;
+
+impl Function for Chain {
+    fn eval<T: Real>(&self, x: &[T]) -> T {
+        let n = x.len();
+        let mut sum = T::zero();
+        for i in 0..n - 1 {
+            let d = x[i + 1] - x[i] * x[i];
+            sum = sum + d * d + x[i] * T::from_f64(0.5);
+        }
+        sum + (x[0] * x[n - 1]).sin()
+    }
+}
+
+#[test]
+fn hessian_sparsity_covers_dense_hessian() {
+    let n = 7;
+    let pattern = hessian_sparsity(&Chain, n);
+    assert_eq!(pattern.row(0), &[0, 1, 6]);
+    assert_eq!(pattern.row(3), &[2, 3, 4]);
+    assert_eq!(pattern.row(6), &[0, 5, 6]);
+    let x: Vec<f64> = (0..n).map(|i| 0.3 + 0.2 * i as f64).collect();
+    let h = hessian(&Chain, &x);
+    for i in 0..n {
+        for j in 0..n {
+            assert_eq!(h[(i, j)] != 0.0, pattern.row(i).contains(&j), "entry ({i}, {j})");
+        }
+    }
+}